@@ -0,0 +1,195 @@
+use bytes::BytesMut;
+use serde_cbor::value::Value as CborValue;
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, event::Value, schema};
+
+/// CBOR tag for epoch-based date/time, per [RFC 8949 §3.4.2][spec].
+///
+/// [spec]: https://www.rfc-editor.org/rfc/rfc8949.html#name-epoch-based-date-time
+const EPOCH_DATETIME_TAG: u64 = 1;
+
+/// Config used to build a `CborSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default)]
+pub struct CborSerializerConfig;
+
+impl CborSerializerConfig {
+    /// Creates a new `CborSerializerConfig`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Build the `CborSerializer` from this configuration.
+    pub fn build(&self) -> CborSerializer {
+        CborSerializer
+    }
+
+    /// The data type of events that are accepted by `CborSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the [CBOR][cbor] format.
+///
+/// [cbor]: https://cbor.io/
+#[derive(Debug, Clone)]
+pub struct CborSerializer;
+
+impl CborSerializer {
+    /// Creates a new `CborSerializer`.
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// Encode the log's fields into a `serde_cbor::Value`, mapping Vector's `Value` variants onto
+    /// the CBOR major types described in RFC 8949.
+    fn event_to_cbor_value(event: &Event) -> CborValue {
+        let log = event.as_log();
+        let map = log
+            .all_fields()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (CborValue::Text(key.to_string()), Self::value_to_cbor(value)))
+            .collect();
+        CborValue::Map(map)
+    }
+
+    fn value_to_cbor(value: &Value) -> CborValue {
+        match value {
+            Value::Bytes(bytes) => match std::str::from_utf8(bytes) {
+                Ok(s) => CborValue::Text(s.to_string()),
+                Err(_) => CborValue::Bytes(bytes.to_vec()),
+            },
+            Value::Regex(regex) => CborValue::Text(regex.as_str().to_string()),
+            Value::Integer(i) => CborValue::Integer(*i as i128),
+            Value::Float(f) => CborValue::Float(f.into_inner()),
+            Value::Boolean(b) => CborValue::Bool(*b),
+            Value::Timestamp(ts) => CborValue::Tag(
+                EPOCH_DATETIME_TAG,
+                Box::new(CborValue::Float(ts.timestamp_nanos_opt().unwrap_or_default() as f64 / 1e9)),
+            ),
+            Value::Array(items) => CborValue::Array(items.iter().map(Self::value_to_cbor).collect()),
+            Value::Object(fields) => CborValue::Map(
+                fields
+                    .iter()
+                    .map(|(k, v)| (CborValue::Text(k.to_string()), Self::value_to_cbor(v)))
+                    .collect(),
+            ),
+            Value::Null => CborValue::Null,
+        }
+    }
+}
+
+impl Encoder<Event> for CborSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let value = Self::event_to_cbor_value(&event);
+        let bytes = serde_cbor::to_vec(&value)?;
+        buffer.extend_from_slice(&bytes);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use chrono::{TimeZone, Utc};
+    use vector_core::event::LogEvent;
+
+    use super::*;
+
+    #[test]
+    fn timestamp_round_trips_through_epoch_tag() {
+        let ts = Utc.timestamp_opt(1_600_000_000, 500_000_000).unwrap();
+        let cbor = CborSerializer::value_to_cbor(&Value::Timestamp(ts));
+
+        let expected_seconds = ts.timestamp_nanos_opt().unwrap() as f64 / 1e9;
+        assert_eq!(
+            cbor,
+            CborValue::Tag(EPOCH_DATETIME_TAG, Box::new(CborValue::Float(expected_seconds)))
+        );
+    }
+
+    #[test]
+    fn nested_maps_and_arrays_convert_recursively() {
+        let mut inner = BTreeMap::new();
+        inner.insert("id".into(), Value::Integer(42));
+        let value = Value::Array(vec![Value::Object(inner), Value::Null]);
+
+        let cbor = CborSerializer::value_to_cbor(&value);
+
+        let expected_inner = CborValue::Map(
+            [(CborValue::Text("id".to_string()), CborValue::Integer(42))]
+                .into_iter()
+                .collect(),
+        );
+        assert_eq!(
+            cbor,
+            CborValue::Array(vec![expected_inner, CborValue::Null])
+        );
+    }
+
+    #[test]
+    fn numeric_variants_convert_to_their_cbor_counterparts() {
+        assert_eq!(
+            CborSerializer::value_to_cbor(&Value::Integer(-7)),
+            CborValue::Integer(-7)
+        );
+        assert_eq!(
+            CborSerializer::value_to_cbor(&Value::Float(3.5.try_into().unwrap())),
+            CborValue::Float(3.5)
+        );
+        assert_eq!(
+            CborSerializer::value_to_cbor(&Value::Boolean(true)),
+            CborValue::Bool(true)
+        );
+    }
+
+    #[test]
+    fn bytes_are_encoded_as_text_when_valid_utf8_and_as_bytes_otherwise() {
+        assert_eq!(
+            CborSerializer::value_to_cbor(&Value::Bytes("hello".into())),
+            CborValue::Text("hello".to_string())
+        );
+        assert_eq!(
+            CborSerializer::value_to_cbor(&Value::Bytes(vec![0xff, 0xfe].into())),
+            CborValue::Bytes(vec![0xff, 0xfe])
+        );
+    }
+
+    #[test]
+    fn event_encodes_to_a_decodable_cbor_map() {
+        let mut log = LogEvent::default();
+        log.insert("message", "hello");
+        log.insert("count", 3);
+
+        let mut buffer = BytesMut::new();
+        CborSerializer::new()
+            .encode(Event::Log(log), &mut buffer)
+            .unwrap();
+
+        let decoded: CborValue = serde_cbor::from_slice(&buffer).unwrap();
+        match decoded {
+            CborValue::Map(map) => {
+                assert_eq!(
+                    map.get(&CborValue::Text("message".to_string())),
+                    Some(&CborValue::Text("hello".to_string()))
+                );
+                assert_eq!(
+                    map.get(&CborValue::Text("count".to_string())),
+                    Some(&CborValue::Integer(3))
+                );
+            }
+            other => panic!("expected a CBOR map, got {other:?}"),
+        }
+    }
+}
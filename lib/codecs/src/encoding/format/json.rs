@@ -0,0 +1,139 @@
+use std::collections::BTreeMap;
+
+use bytes::{BufMut, BytesMut};
+use serde_json::ser::{CompactFormatter, PrettyFormatter};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, schema};
+
+/// Indentation to use when `pretty` output is enabled.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum JsonIndentation {
+    /// Indent with a fixed number of spaces.
+    Spaces(u8),
+
+    /// Indent with a single tab character per level.
+    Tabs,
+}
+
+impl Default for JsonIndentation {
+    fn default() -> Self {
+        Self::Spaces(2)
+    }
+}
+
+/// Config used to build a `JsonSerializer`.
+#[configurable_component]
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct JsonSerializerConfig {
+    /// Whether to use human-readable, indented JSON output instead of the default compact form.
+    #[serde(default)]
+    pub pretty: bool,
+
+    /// Whether to sort object keys alphabetically, for diff-friendly, deterministic output.
+    #[serde(default)]
+    pub sort_keys: bool,
+
+    /// The indentation to use when `pretty` is enabled. Has no effect otherwise.
+    #[serde(default)]
+    pub indentation: JsonIndentation,
+}
+
+impl JsonSerializerConfig {
+    /// Creates a new `JsonSerializerConfig`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the `JsonSerializer` from this configuration.
+    pub fn build(&self) -> JsonSerializer {
+        JsonSerializer {
+            pretty: self.pretty,
+            sort_keys: self.sort_keys,
+            indentation: self.indentation,
+        }
+    }
+
+    /// The data type of events that are accepted by `JsonSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using the JSON format.
+#[derive(Debug, Clone, Default)]
+pub struct JsonSerializer {
+    pretty: bool,
+    sort_keys: bool,
+    indentation: JsonIndentation,
+}
+
+impl JsonSerializer {
+    /// Creates a new `JsonSerializer`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Encode event and represent it as a JSON value.
+    pub fn to_json_value(&self, event: Event) -> Result<serde_json::Value, vector_common::Error> {
+        let log = event.into_log();
+        serde_json::to_value(&log).map_err(Into::into)
+    }
+
+    fn sorted(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let sorted: BTreeMap<String, serde_json::Value> = map
+                    .into_iter()
+                    .map(|(key, value)| (key, Self::sorted(value)))
+                    .collect();
+                serde_json::Value::Object(sorted.into_iter().collect())
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(Self::sorted).collect())
+            }
+            other => other,
+        }
+    }
+}
+
+impl Encoder<Event> for JsonSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut value = self.to_json_value(event)?;
+        if self.sort_keys {
+            value = Self::sorted(value);
+        }
+
+        let mut writer = buffer.writer();
+        if self.pretty {
+            match self.indentation {
+                JsonIndentation::Spaces(width) => {
+                    let indent = " ".repeat(width as usize);
+                    let formatter = PrettyFormatter::with_indent(indent.as_bytes());
+                    let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+                    serde::Serialize::serialize(&value, &mut serializer)?;
+                }
+                JsonIndentation::Tabs => {
+                    let formatter = PrettyFormatter::with_indent(b"\t");
+                    let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+                    serde::Serialize::serialize(&value, &mut serializer)?;
+                }
+            }
+        } else {
+            let formatter = CompactFormatter;
+            let mut serializer = serde_json::Serializer::with_formatter(&mut writer, formatter);
+            serde::Serialize::serialize(&value, &mut serializer)?;
+        }
+
+        Ok(())
+    }
+}
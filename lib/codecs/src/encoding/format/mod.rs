@@ -0,0 +1,9 @@
+//! Serialization formats for turning events into bytes.
+
+mod cbor;
+mod json;
+mod protobuf;
+
+pub use cbor::{CborSerializer, CborSerializerConfig};
+pub use json::{JsonIndentation, JsonSerializer, JsonSerializerConfig};
+pub use protobuf::{ProtobufSerializer, ProtobufSerializerConfig};
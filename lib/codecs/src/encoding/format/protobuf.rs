@@ -0,0 +1,444 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use bytes::BytesMut;
+use prost::Message as _;
+use prost_reflect::{Cardinality, DescriptorPool, DynamicMessage, MessageDescriptor, Value};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+use vector_core::{config::DataType, event::Event, event::Value as EventValue, schema};
+
+/// Name of the well-known `google.protobuf.Timestamp` message type.
+const WELL_KNOWN_TIMESTAMP: &str = "google.protobuf.Timestamp";
+
+/// Config used to build a `ProtobufSerializer`.
+#[configurable_component]
+#[derive(Clone, Debug)]
+pub struct ProtobufSerializerConfig {
+    /// Path to a compiled [`FileDescriptorSet`][descriptor_set] (`.desc`/`.pb`) describing the
+    /// target message.
+    ///
+    /// [descriptor_set]: https://protobuf.dev/reference/java/java-generated-code/#descriptors
+    pub desc_file: PathBuf,
+
+    /// The fully-qualified name of the message type to encode events as, e.g.
+    /// `mypackage.MyMessage`.
+    pub message_type: String,
+
+    /// Maps event field names onto protobuf field names, for cases where they differ.
+    ///
+    /// Event fields that aren't listed here are mapped onto a proto field of the same name. The
+    /// same mapping is applied at every nesting level, so an entry also renames a field with a
+    /// matching name inside a nested message.
+    #[serde(default)]
+    pub field_mapping: HashMap<String, String>,
+}
+
+impl ProtobufSerializerConfig {
+    /// Creates a new `ProtobufSerializerConfig`.
+    pub fn new(
+        desc_file: PathBuf,
+        message_type: String,
+        field_mapping: HashMap<String, String>,
+    ) -> Self {
+        Self {
+            desc_file,
+            message_type,
+            field_mapping,
+        }
+    }
+
+    /// Build the `ProtobufSerializer` from this configuration.
+    pub fn build(
+        &self,
+    ) -> Result<ProtobufSerializer, Box<dyn std::error::Error + Send + Sync + 'static>> {
+        let bytes = std::fs::read(&self.desc_file).map_err(|error| {
+            format!(
+                "failed to read descriptor set {:?}: {error}",
+                self.desc_file
+            )
+        })?;
+        let pool = DescriptorPool::decode(bytes.as_slice())
+            .map_err(|error| format!("failed to decode descriptor set: {error}"))?;
+        let message_descriptor = pool.get_message_by_name(&self.message_type).ok_or_else(|| {
+            format!(
+                "message type {:?} not found in descriptor set {:?}",
+                self.message_type, self.desc_file
+            )
+        })?;
+
+        Ok(ProtobufSerializer {
+            message_descriptor,
+            field_mapping: self.field_mapping.clone(),
+        })
+    }
+
+    /// The data type of events that are accepted by `ProtobufSerializer`.
+    pub fn input_type(&self) -> DataType {
+        DataType::Log
+    }
+
+    /// The schema required by the serializer.
+    ///
+    /// This is always empty: proto2 `required` fields are not turned into schema requirements,
+    /// and `encode` does not check for them either, so a message missing a required field is
+    /// encoded without it.
+    pub fn schema_requirement(&self) -> schema::Requirement {
+        schema::Requirement::empty()
+    }
+}
+
+/// Serializer that converts an `Event` to bytes using a user-supplied protobuf schema.
+///
+/// Unlike [`super::NativeSerializer`], which always encodes Vector's own `event.proto`, this
+/// serializer maps log-event fields onto an arbitrary message type loaded from a compiled
+/// [`FileDescriptorSet`].
+#[derive(Debug, Clone)]
+pub struct ProtobufSerializer {
+    message_descriptor: MessageDescriptor,
+    field_mapping: HashMap<String, String>,
+}
+
+impl ProtobufSerializer {
+    /// Returns the [`MessageDescriptor`] this serializer encodes events as.
+    pub fn message_descriptor(&self) -> &MessageDescriptor {
+        &self.message_descriptor
+    }
+
+    fn proto_field_name<'a>(&'a self, event_field: &'a str) -> &'a str {
+        self.field_mapping
+            .get(event_field)
+            .map(String::as_str)
+            .unwrap_or(event_field)
+    }
+
+    fn build_message(
+        descriptor: &MessageDescriptor,
+        fields: impl Iterator<Item = (String, EventValue)>,
+        field_mapping: &HashMap<String, String>,
+    ) -> Result<DynamicMessage, vector_common::Error> {
+        let mut message = DynamicMessage::new(descriptor.clone());
+
+        for (key, value) in fields {
+            let proto_name = field_mapping.get(&key).map(String::as_str).unwrap_or(&key);
+            let Some(field) = descriptor.get_field_by_name(proto_name) else {
+                continue;
+            };
+
+            let encoded = Self::encode_field_value(&field, &value, field_mapping)?;
+            message.set_field(&field, encoded);
+        }
+
+        Ok(message)
+    }
+
+    fn encode_field_value(
+        field: &prost_reflect::FieldDescriptor,
+        value: &EventValue,
+        field_mapping: &HashMap<String, String>,
+    ) -> Result<Value, vector_common::Error> {
+        if field.cardinality() == Cardinality::Repeated {
+            let EventValue::Array(items) = value else {
+                return Err(format!("field {:?} is repeated but value is not an array", field.name()).into());
+            };
+            let encoded = items
+                .iter()
+                .map(|item| Self::encode_scalar_or_message(field, item, field_mapping))
+                .collect::<Result<Vec<_>, _>>()?;
+            return Ok(Value::List(encoded));
+        }
+
+        Self::encode_scalar_or_message(field, value, field_mapping)
+    }
+
+    fn encode_scalar_or_message(
+        field: &prost_reflect::FieldDescriptor,
+        value: &EventValue,
+        field_mapping: &HashMap<String, String>,
+    ) -> Result<Value, vector_common::Error> {
+        use prost_reflect::Kind;
+
+        match field.kind() {
+            Kind::Message(nested) if nested.full_name() == WELL_KNOWN_TIMESTAMP => {
+                let EventValue::Timestamp(ts) = value else {
+                    return Err(format!("field {:?} expects a timestamp", field.name()).into());
+                };
+                let mut message = DynamicMessage::new(nested);
+                message.set_field_by_name("seconds", Value::I64(ts.timestamp()));
+                message.set_field_by_name("nanos", Value::I32(ts.timestamp_subsec_nanos() as i32));
+                Ok(Value::Message(message))
+            }
+            Kind::Message(nested) => {
+                let EventValue::Object(fields) = value else {
+                    return Err(format!("field {:?} expects a nested message", field.name()).into());
+                };
+                // Reuse the same top-level `field_mapping` for nested messages, so a renamed
+                // field name applies wherever it's encountered in the event, not just at the
+                // top level.
+                let message = Self::build_message(
+                    &nested,
+                    fields.iter().map(|(k, v)| (k.to_string(), v.clone())),
+                    field_mapping,
+                )?;
+                Ok(Value::Message(message))
+            }
+            Kind::Bool => Ok(Value::Bool(coerce_bool(value)?)),
+            Kind::Int32 | Kind::Sint32 | Kind::Sfixed32 => Ok(Value::I32(coerce_i64(value)? as i32)),
+            Kind::Int64 | Kind::Sint64 | Kind::Sfixed64 => Ok(Value::I64(coerce_i64(value)?)),
+            Kind::Uint32 | Kind::Fixed32 => Ok(Value::U32(coerce_i64(value)? as u32)),
+            Kind::Uint64 | Kind::Fixed64 => Ok(Value::U64(coerce_i64(value)? as u64)),
+            Kind::Float => Ok(Value::F32(coerce_f64(value)? as f32)),
+            Kind::Double => Ok(Value::F64(coerce_f64(value)?)),
+            Kind::String => Ok(Value::String(coerce_string(value)?)),
+            Kind::Bytes => Ok(Value::Bytes(coerce_bytes(value)?)),
+            Kind::Enum(_) => Ok(Value::EnumNumber(coerce_i64(value)? as i32)),
+        }
+    }
+}
+
+fn coerce_bool(value: &EventValue) -> Result<bool, vector_common::Error> {
+    match value {
+        EventValue::Boolean(b) => Ok(*b),
+        _ => Err("expected a boolean value".into()),
+    }
+}
+
+fn coerce_i64(value: &EventValue) -> Result<i64, vector_common::Error> {
+    match value {
+        EventValue::Integer(i) => Ok(*i),
+        EventValue::Float(f) => Ok(f.into_inner() as i64),
+        _ => Err("expected a numeric value".into()),
+    }
+}
+
+fn coerce_f64(value: &EventValue) -> Result<f64, vector_common::Error> {
+    match value {
+        EventValue::Float(f) => Ok(f.into_inner()),
+        EventValue::Integer(i) => Ok(*i as f64),
+        _ => Err("expected a numeric value".into()),
+    }
+}
+
+fn coerce_string(value: &EventValue) -> Result<String, vector_common::Error> {
+    match value {
+        EventValue::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        _ => Err("expected a string value".into()),
+    }
+}
+
+fn coerce_bytes(value: &EventValue) -> Result<Vec<u8>, vector_common::Error> {
+    match value {
+        EventValue::Bytes(bytes) => Ok(bytes.to_vec()),
+        _ => Err("expected a byte string value".into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use prost_reflect::Value as ProstValue;
+    use prost_types::{
+        field_descriptor_proto::{Label, Type},
+        DescriptorProto, FieldDescriptorProto, FileDescriptorProto, FileDescriptorSet,
+    };
+
+    use super::*;
+
+    fn field(name: &str, number: i32, kind: Type, type_name: Option<&str>) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            name: Some(name.to_string()),
+            number: Some(number),
+            label: Some(Label::Optional as i32),
+            r#type: Some(kind as i32),
+            type_name: type_name.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn repeated_field(
+        name: &str,
+        number: i32,
+        kind: Type,
+        type_name: Option<&str>,
+    ) -> FieldDescriptorProto {
+        FieldDescriptorProto {
+            label: Some(Label::Repeated as i32),
+            ..field(name, number, kind, type_name)
+        }
+    }
+
+    /// Hand-rolled `DescriptorPool` standing in for a compiled `.desc` file: `test.Tag { name }`,
+    /// `test.Container { tag: Tag }`, and a fake `google.protobuf.Timestamp { seconds, nanos }`
+    /// that only needs to match by full name for [`ProtobufSerializer`]'s well-known-type check.
+    fn test_pool() -> DescriptorPool {
+        let timestamp_file = FileDescriptorProto {
+            name: Some("google/protobuf/timestamp.proto".to_string()),
+            package: Some("google.protobuf".to_string()),
+            syntax: Some("proto3".to_string()),
+            message_type: vec![DescriptorProto {
+                name: Some("Timestamp".to_string()),
+                field: vec![
+                    field("seconds", 1, Type::Int64, None),
+                    field("nanos", 2, Type::Int32, None),
+                ],
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+
+        let test_file = FileDescriptorProto {
+            name: Some("test.proto".to_string()),
+            package: Some("test".to_string()),
+            dependency: vec!["google/protobuf/timestamp.proto".to_string()],
+            syntax: Some("proto3".to_string()),
+            message_type: vec![
+                DescriptorProto {
+                    name: Some("Tag".to_string()),
+                    field: vec![field("name", 1, Type::String, None)],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("Container".to_string()),
+                    field: vec![
+                        field("tag", 1, Type::Message, Some(".test.Tag")),
+                        repeated_field("tags", 2, Type::Message, Some(".test.Tag")),
+                    ],
+                    ..Default::default()
+                },
+                DescriptorProto {
+                    name: Some("WithTimestamp".to_string()),
+                    field: vec![field(
+                        "created_at",
+                        1,
+                        Type::Message,
+                        Some(".google.protobuf.Timestamp"),
+                    )],
+                    ..Default::default()
+                },
+            ],
+            ..Default::default()
+        };
+
+        DescriptorPool::from_file_descriptor_set(FileDescriptorSet {
+            file: vec![timestamp_file, test_file],
+        })
+        .expect("hand-written FileDescriptorSet should be valid")
+    }
+
+    #[test]
+    fn nested_message_field_reuses_the_top_level_field_mapping() {
+        let pool = test_pool();
+        let container = pool.get_message_by_name("test.Container").unwrap();
+
+        let mut field_mapping = HashMap::new();
+        field_mapping.insert("box".to_string(), "tag".to_string());
+        field_mapping.insert("label".to_string(), "name".to_string());
+
+        let mut inner = BTreeMap::new();
+        inner.insert("label".into(), EventValue::Bytes("hello".into()));
+
+        let fields = vec![("box".to_string(), EventValue::Object(inner))];
+        let message =
+            ProtobufSerializer::build_message(&container, fields.into_iter(), &field_mapping)
+                .unwrap();
+
+        let ProstValue::Message(tag_message) = message.get_field_by_name("tag").unwrap().into_owned()
+        else {
+            panic!("expected a nested message value");
+        };
+        assert_eq!(
+            tag_message.get_field_by_name("name").unwrap().into_owned(),
+            ProstValue::String("hello".to_string())
+        );
+    }
+
+    #[test]
+    fn repeated_nested_message_field_encodes_each_array_element() {
+        let pool = test_pool();
+        let container = pool.get_message_by_name("test.Container").unwrap();
+
+        let mut first = BTreeMap::new();
+        first.insert("name".into(), EventValue::Bytes("a".into()));
+        let mut second = BTreeMap::new();
+        second.insert("name".into(), EventValue::Bytes("b".into()));
+
+        let fields = vec![(
+            "tags".to_string(),
+            EventValue::Array(vec![EventValue::Object(first), EventValue::Object(second)]),
+        )];
+        let message =
+            ProtobufSerializer::build_message(&container, fields.into_iter(), &HashMap::new())
+                .unwrap();
+
+        let ProstValue::List(tags) = message.get_field_by_name("tags").unwrap().into_owned() else {
+            panic!("expected a repeated field value");
+        };
+        assert_eq!(tags.len(), 2);
+    }
+
+    #[test]
+    fn well_known_timestamp_field_encodes_seconds_and_nanos() {
+        let pool = test_pool();
+        let with_ts = pool.get_message_by_name("test.WithTimestamp").unwrap();
+
+        let ts = chrono::DateTime::from_timestamp(1_600_000_000, 500_000_000).unwrap();
+        let fields = vec![("created_at".to_string(), EventValue::Timestamp(ts))];
+        let message =
+            ProtobufSerializer::build_message(&with_ts, fields.into_iter(), &HashMap::new())
+                .unwrap();
+
+        let ProstValue::Message(ts_message) =
+            message.get_field_by_name("created_at").unwrap().into_owned()
+        else {
+            panic!("expected a nested timestamp message");
+        };
+        assert_eq!(
+            ts_message.get_field_by_name("seconds").unwrap().into_owned(),
+            ProstValue::I64(1_600_000_000)
+        );
+        assert_eq!(
+            ts_message.get_field_by_name("nanos").unwrap().into_owned(),
+            ProstValue::I32(500_000_000)
+        );
+    }
+
+    #[test]
+    fn scalar_fields_coerce_from_event_values() {
+        let pool = test_pool();
+        let tag = pool.get_message_by_name("test.Tag").unwrap();
+
+        let fields = vec![("name".to_string(), EventValue::Bytes("widget".into()))];
+        let message =
+            ProtobufSerializer::build_message(&tag, fields.into_iter(), &HashMap::new()).unwrap();
+
+        assert_eq!(
+            message.get_field_by_name("name").unwrap().into_owned(),
+            ProstValue::String("widget".to_string())
+        );
+    }
+}
+
+impl Encoder<Event> for ProtobufSerializer {
+    type Error = vector_common::Error;
+
+    fn encode(&mut self, event: Event, buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let log = event.as_log();
+        // Top-level fields only: nested objects and arrays are kept whole so they can become
+        // nested messages and repeated fields.
+        let fields = log
+            .as_map()
+            .into_iter()
+            .flatten()
+            .map(|(key, value)| (self.proto_field_name(&key.to_string()).to_owned(), value.clone()));
+
+        let message = Self::build_message(&self.message_descriptor, fields, &self.field_mapping)?;
+
+        let mut bytes = Vec::with_capacity(message.encoded_len());
+        message
+            .encode(&mut bytes)
+            .map_err(|error| format!("failed to encode protobuf message: {error}"))?;
+        buffer.extend_from_slice(&bytes);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,212 @@
+use std::path::PathBuf;
+
+use aead::{Aead, KeyInit};
+use aes_gcm::Aes256Gcm;
+use chacha20poly1305::ChaCha20Poly1305;
+use bytes::BytesMut;
+use rand::{rngs::OsRng, RngCore};
+use tokio_util::codec::Encoder;
+use vector_config::configurable_component;
+
+use super::super::{BoxedFramingError, BuildError, Framer, FramingConfig};
+
+/// Size, in bytes, of a 256-bit AEAD key.
+const KEY_LEN: usize = 32;
+
+/// Size, in bytes, of a 96-bit AEAD nonce.
+const NONCE_LEN: usize = 12;
+
+/// The AEAD algorithm used to encrypt framed event bytes.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum AeadAlgorithm {
+    /// [ChaCha20-Poly1305][chacha20poly1305].
+    ///
+    /// [chacha20poly1305]: https://datatracker.ietf.org/doc/html/rfc8439
+    ChaCha20Poly1305,
+
+    /// AES-256 in [Galois/Counter Mode][aes_gcm].
+    ///
+    /// [aes_gcm]: https://csrc.nist.gov/publications/detail/sp/800-38d/final
+    Aes256Gcm,
+}
+
+/// Where the symmetric encryption key is read from.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum KeySource {
+    /// The key is given inline, base64-encoded.
+    Inline {
+        /// The base64-encoded key.
+        key: String,
+    },
+
+    /// The key is read from a file on disk, base64-encoded.
+    File {
+        /// Path to the file containing the base64-encoded key.
+        path: PathBuf,
+    },
+
+    /// The key is read from an environment variable, base64-encoded.
+    EnvVar {
+        /// Name of the environment variable containing the base64-encoded key.
+        name: String,
+    },
+}
+
+impl KeySource {
+    fn load(&self) -> Result<Vec<u8>, BuildError> {
+        let encoded = match self {
+            Self::Inline { key } => key.clone(),
+            Self::File { path } => std::fs::read_to_string(path)
+                .map_err(|error| format!("failed to read key file {path:?}: {error}"))?
+                .trim()
+                .to_owned(),
+            Self::EnvVar { name } => std::env::var(name)
+                .map_err(|error| format!("failed to read key from env var {name:?}: {error}"))?,
+        };
+
+        let key = base64::decode(encoded.trim())
+            .map_err(|error| format!("key is not valid base64: {error}"))?;
+
+        if key.len() != KEY_LEN {
+            return Err(format!(
+                "key must be {KEY_LEN} bytes after base64 decoding, got {}",
+                key.len()
+            )
+            .into());
+        }
+
+        Ok(key)
+    }
+}
+
+/// How the per-frame nonce is produced.
+///
+/// # Invariant
+///
+/// A nonce must never repeat for a given key. [`NonceStrategy::Random`] draws a fresh 96-bit
+/// nonce from the OS RNG for every frame, which is safe to pair with a long-lived static key.
+/// [`NonceStrategy::Counter`] derives the nonce deterministically and is only safe when the key
+/// itself is unique per stream (e.g. freshly generated per connection); building a
+/// `EncryptedFramingConfig` that pairs `Counter` with a static [`KeySource`] is rejected, since
+/// this repo has no mechanism for guaranteeing per-stream key uniqueness.
+#[configurable_component]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NonceStrategy {
+    /// Draw a new random 96-bit nonce for every frame.
+    Random,
+
+    /// Derive the nonce from a monotonically increasing counter.
+    Counter,
+}
+
+/// Wraps another `FramingConfig`, encrypting the framed byte stream it produces.
+#[configurable_component]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EncryptedFramingConfig {
+    /// The framing to apply before encrypting.
+    pub inner: Box<FramingConfig>,
+
+    /// The AEAD algorithm to encrypt with.
+    pub algorithm: AeadAlgorithm,
+
+    /// Where to read the symmetric key from.
+    pub key: KeySource,
+
+    /// How the per-frame nonce is produced.
+    #[serde(default = "default_nonce_strategy")]
+    pub nonce_strategy: NonceStrategy,
+}
+
+const fn default_nonce_strategy() -> NonceStrategy {
+    NonceStrategy::Random
+}
+
+impl EncryptedFramingConfig {
+    /// Build the `EncryptedFramer` from this configuration.
+    pub fn build(&self) -> Result<EncryptedFramer, BuildError> {
+        if matches!(self.nonce_strategy, NonceStrategy::Counter) {
+            return Err("NonceStrategy::Counter requires a key that is unique per stream; \
+                 pair a static KeySource with NonceStrategy::Random instead"
+                .into());
+        }
+
+        let key_bytes = self.key.load()?;
+        let cipher = match self.algorithm {
+            AeadAlgorithm::ChaCha20Poly1305 => AeadCipher::ChaCha20Poly1305(
+                ChaCha20Poly1305::new_from_slice(&key_bytes)
+                    .map_err(|error| format!("invalid key: {error}"))?,
+            ),
+            AeadAlgorithm::Aes256Gcm => AeadCipher::Aes256Gcm(
+                Aes256Gcm::new_from_slice(&key_bytes)
+                    .map_err(|error| format!("invalid key: {error}"))?,
+            ),
+        };
+
+        Ok(EncryptedFramer {
+            inner: Box::new(self.inner.build()?),
+            cipher,
+        })
+    }
+}
+
+#[derive(Clone)]
+enum AeadCipher {
+    ChaCha20Poly1305(ChaCha20Poly1305),
+    Aes256Gcm(Aes256Gcm),
+}
+
+impl AeadCipher {
+    fn encrypt(&self, nonce: &[u8; NONCE_LEN], plaintext: &[u8]) -> Result<Vec<u8>, aead::Error> {
+        match self {
+            Self::ChaCha20Poly1305(cipher) => cipher.encrypt(nonce.into(), plaintext),
+            Self::Aes256Gcm(cipher) => cipher.encrypt(nonce.into(), plaintext),
+        }
+    }
+}
+
+/// Framer that encrypts the byte stream produced by an inner `Framer`.
+///
+/// Each call to `encode` emits `nonce || ciphertext || tag` for that frame, where `nonce` is
+/// drawn fresh from the OS RNG on every call (see [`NonceStrategy`]) rather than derived from any
+/// state carried on `self`. Cloning an `EncryptedFramer` is therefore safe: the clone holds the
+/// same key and inner framer but no nonce history to replay, so it draws its own independent
+/// nonces just like the original. The corresponding decode-side reversal belongs in the sibling
+/// decoding module, which is not part of this checkout.
+#[derive(Clone)]
+pub struct EncryptedFramer {
+    inner: Box<Framer>,
+    cipher: AeadCipher,
+}
+
+impl std::fmt::Debug for EncryptedFramer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EncryptedFramer").finish_non_exhaustive()
+    }
+}
+
+impl Encoder<()> for EncryptedFramer {
+    type Error = BoxedFramingError;
+
+    fn encode(&mut self, _: (), buffer: &mut BytesMut) -> Result<(), Self::Error> {
+        let mut framed = buffer.split();
+        self.inner.encode((), &mut framed)?;
+
+        let mut nonce = [0u8; NONCE_LEN];
+        OsRng.fill_bytes(&mut nonce);
+
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, &framed)
+            .map_err(|_| -> BoxedFramingError { "failed to encrypt frame".into() })?;
+
+        buffer.extend_from_slice(&nonce);
+        buffer.extend_from_slice(&ciphertext);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,11 @@
+//! Byte-framing strategies for delimiting a stream of serialized events.
+
+mod compression;
+mod encryption;
+
+pub use compression::{
+    CompressedFramer, CompressedFramingConfig, CompressionAlgorithm, CompressionLevel,
+};
+pub use encryption::{
+    AeadAlgorithm, EncryptedFramer, EncryptedFramingConfig, KeySource, NonceStrategy,
+};
@@ -8,16 +8,20 @@ use std::fmt::Debug;
 
 use bytes::BytesMut;
 pub use format::{
-    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, GelfSerializer,
-    GelfSerializerConfig, JsonSerializer, JsonSerializerConfig, LogfmtSerializer,
-    LogfmtSerializerConfig, NativeJsonSerializer, NativeJsonSerializerConfig, NativeSerializer,
-    NativeSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
+    AvroSerializer, AvroSerializerConfig, AvroSerializerOptions, CborSerializer,
+    CborSerializerConfig, GelfSerializer, GelfSerializerConfig, JsonIndentation, JsonSerializer,
+    JsonSerializerConfig, LogfmtSerializer, LogfmtSerializerConfig, NativeJsonSerializer,
+    NativeJsonSerializerConfig, NativeSerializer, NativeSerializerConfig, ProtobufSerializer,
+    ProtobufSerializerConfig, RawMessageSerializer, RawMessageSerializerConfig, TextSerializer,
     TextSerializerConfig, SyslogSerializer, SyslogSerializerConfig,
 };
 pub use framing::{
-    BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig, CharacterDelimitedEncoder,
-    CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions, LengthDelimitedEncoder,
+    AeadAlgorithm, BoxedFramer, BoxedFramingError, BytesEncoder, BytesEncoderConfig,
+    CharacterDelimitedEncoder, CharacterDelimitedEncoderConfig, CharacterDelimitedEncoderOptions,
+    CompressedFramer, CompressedFramingConfig, CompressionAlgorithm, CompressionLevel,
+    EncryptedFramer, EncryptedFramingConfig, KeySource, LengthDelimitedEncoder,
     LengthDelimitedEncoderConfig, NewlineDelimitedEncoder, NewlineDelimitedEncoderConfig,
+    NonceStrategy,
 };
 use vector_config::configurable_component;
 use vector_core::{config::DataType, event::Event, schema};
@@ -73,6 +77,18 @@ pub enum FramingConfig {
 
     /// Event data is delimited by a newline (LF) character.
     NewlineDelimited,
+
+    /// Frames produced by another `FramingConfig` are compressed as a single stream.
+    Compressed(
+        /// Options for the compressed framer.
+        CompressedFramingConfig,
+    ),
+
+    /// Frames produced by another `FramingConfig` are encrypted before being emitted.
+    Encrypted(
+        /// Options for the encrypted framer.
+        EncryptedFramingConfig,
+    ),
 }
 
 impl From<BytesEncoderConfig> for FramingConfig {
@@ -101,10 +117,22 @@ impl From<NewlineDelimitedEncoderConfig> for FramingConfig {
     }
 }
 
+impl From<CompressedFramingConfig> for FramingConfig {
+    fn from(config: CompressedFramingConfig) -> Self {
+        Self::Compressed(config)
+    }
+}
+
+impl From<EncryptedFramingConfig> for FramingConfig {
+    fn from(config: EncryptedFramingConfig) -> Self {
+        Self::Encrypted(config)
+    }
+}
+
 impl FramingConfig {
     /// Build the `Framer` from this configuration.
-    pub fn build(&self) -> Framer {
-        match self {
+    pub fn build(&self) -> Result<Framer, BuildError> {
+        Ok(match self {
             FramingConfig::Bytes => Framer::Bytes(BytesEncoderConfig.build()),
             FramingConfig::CharacterDelimited {
                 character_delimited,
@@ -120,7 +148,9 @@ impl FramingConfig {
             FramingConfig::NewlineDelimited => {
                 Framer::NewlineDelimited(NewlineDelimitedEncoderConfig.build())
             }
-        }
+            FramingConfig::Compressed(config) => Framer::Compressed(config.build()?),
+            FramingConfig::Encrypted(config) => Framer::Encrypted(config.build()?),
+        })
     }
 }
 
@@ -137,6 +167,10 @@ pub enum Framer {
     NewlineDelimited(NewlineDelimitedEncoder),
     /// Uses an opaque `Encoder` implementation for framing.
     Boxed(BoxedFramer),
+    /// Uses a `CompressedFramer` for framing.
+    Compressed(CompressedFramer),
+    /// Uses an `EncryptedFramer` for framing.
+    Encrypted(EncryptedFramer),
 }
 
 impl From<BytesEncoder> for Framer {
@@ -169,6 +203,18 @@ impl From<BoxedFramer> for Framer {
     }
 }
 
+impl From<CompressedFramer> for Framer {
+    fn from(framer: CompressedFramer) -> Self {
+        Self::Compressed(framer)
+    }
+}
+
+impl From<EncryptedFramer> for Framer {
+    fn from(framer: EncryptedFramer) -> Self {
+        Self::Encrypted(framer)
+    }
+}
+
 impl tokio_util::codec::Encoder<()> for Framer {
     type Error = BoxedFramingError;
 
@@ -179,11 +225,18 @@ impl tokio_util::codec::Encoder<()> for Framer {
             Framer::LengthDelimited(framer) => framer.encode((), buffer),
             Framer::NewlineDelimited(framer) => framer.encode((), buffer),
             Framer::Boxed(framer) => framer.encode((), buffer),
+            Framer::Compressed(framer) => framer.encode((), buffer),
+            Framer::Encrypted(framer) => framer.encode((), buffer),
         }
     }
 }
 
 /// Serializer configuration.
+///
+/// This is internally tagged on `codec`, and every variant wraps a struct (or is a unit variant),
+/// so serde flattens each variant's options alongside the `codec` field rather than nesting them
+/// under a variant-named key -- e.g. `codec: json` plus a sibling `pretty: true`, not
+/// `codec: json` plus a nested `json: { pretty: true }` block.
 #[configurable_component]
 #[derive(Clone, Debug)]
 #[serde(tag = "codec", rename_all = "snake_case")]
@@ -202,6 +255,11 @@ pub enum SerializerConfig {
     /// [gelf]: https://docs.graylog.org/docs/gelf
     Gelf,
 
+    /// Encodes an event as a [CBOR][cbor] map.
+    ///
+    /// [cbor]: https://cbor.io/
+    Cbor,
+
     /// Encodes an event as [JSON][json].
     ///
     /// [json]: https://www.json.org/
@@ -257,7 +315,19 @@ pub enum SerializerConfig {
     /// RFC 3164 and 5424 are supported
     Syslog (
         SyslogSerializerConfig,
-    )
+    ),
+
+    /// Encodes an event as a [Protocol Buffers][protobuf] message, using a user-supplied schema.
+    ///
+    /// Unlike [`SerializerConfig::Native`], which always emits Vector's own `event.proto`, this
+    /// variant maps log-event fields onto an arbitrary message type loaded from a compiled
+    /// `FileDescriptorSet`.
+    ///
+    /// [protobuf]: https://protobuf.dev/
+    Protobuf(
+        /// Protobuf-specific encoder options.
+        ProtobufSerializerConfig,
+    ),
 }
 
 impl From<AvroSerializerConfig> for SerializerConfig {
@@ -272,6 +342,12 @@ impl From<GelfSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<CborSerializerConfig> for SerializerConfig {
+    fn from(_: CborSerializerConfig) -> Self {
+        Self::Cbor
+    }
+}
+
 impl From<JsonSerializerConfig> for SerializerConfig {
     fn from(config: JsonSerializerConfig) -> Self {
         Self::Json(config)
@@ -314,6 +390,12 @@ impl From<SyslogSerializerConfig> for SerializerConfig {
     }
 }
 
+impl From<ProtobufSerializerConfig> for SerializerConfig {
+    fn from(config: ProtobufSerializerConfig) -> Self {
+        Self::Protobuf(config)
+    }
+}
+
 impl SerializerConfig {
     /// Build the `Serializer` from this configuration.
     pub fn build(&self) -> Result<Serializer, Box<dyn std::error::Error + Send + Sync + 'static>> {
@@ -322,6 +404,7 @@ impl SerializerConfig {
                 AvroSerializerConfig::new(avro.schema.clone()).build()?,
             )),
             SerializerConfig::Gelf => Ok(Serializer::Gelf(GelfSerializerConfig::new().build())),
+            SerializerConfig::Cbor => Ok(Serializer::Cbor(CborSerializerConfig::new().build())),
             SerializerConfig::Json(config) => Ok(Serializer::Json(config.build())),
             SerializerConfig::Logfmt => Ok(Serializer::Logfmt(LogfmtSerializerConfig.build())),
             SerializerConfig::Native => Ok(Serializer::Native(NativeSerializerConfig.build())),
@@ -333,6 +416,7 @@ impl SerializerConfig {
             }
             SerializerConfig::Text(config) => Ok(Serializer::Text(config.build())),
             SerializerConfig::Syslog(config) => Ok(Serializer::Syslog(config.build())),
+            SerializerConfig::Protobuf(config) => Ok(Serializer::Protobuf(config.build()?)),
         }
     }
 
@@ -353,6 +437,11 @@ impl SerializerConfig {
             SerializerConfig::Avro { .. } | SerializerConfig::Native => {
                 FramingConfig::LengthDelimited
             }
+            // CBOR items are self-delimiting, but a stream of them still needs frame boundaries
+            // for consumers to know where one item ends and the next begins.
+            SerializerConfig::Cbor => FramingConfig::LengthDelimited,
+            // Protobuf messages aren't self-delimiting either.
+            SerializerConfig::Protobuf(_) => FramingConfig::LengthDelimited,
             SerializerConfig::Gelf
             | SerializerConfig::Json(_)
             | SerializerConfig::Logfmt
@@ -370,6 +459,7 @@ impl SerializerConfig {
                 AvroSerializerConfig::new(avro.schema.clone()).input_type()
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::input_type(),
+            SerializerConfig::Cbor => CborSerializerConfig::new().input_type(),
             SerializerConfig::Json(config) => config.input_type(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.input_type(),
             SerializerConfig::Native => NativeSerializerConfig.input_type(),
@@ -377,6 +467,7 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => RawMessageSerializerConfig.input_type(),
             SerializerConfig::Text(config) => config.input_type(),
             SerializerConfig::Syslog(config) => config.input_type(),
+            SerializerConfig::Protobuf(config) => config.input_type(),
         }
     }
 
@@ -387,6 +478,7 @@ impl SerializerConfig {
                 AvroSerializerConfig::new(avro.schema.clone()).schema_requirement()
             }
             SerializerConfig::Gelf { .. } => GelfSerializerConfig::schema_requirement(),
+            SerializerConfig::Cbor => CborSerializerConfig::new().schema_requirement(),
             SerializerConfig::Json(config) => config.schema_requirement(),
             SerializerConfig::Logfmt => LogfmtSerializerConfig.schema_requirement(),
             SerializerConfig::Native => NativeSerializerConfig.schema_requirement(),
@@ -394,6 +486,7 @@ impl SerializerConfig {
             SerializerConfig::RawMessage => RawMessageSerializerConfig.schema_requirement(),
             SerializerConfig::Text(config) => config.schema_requirement(),
             SerializerConfig::Syslog(config) => config.schema_requirement(),
+            SerializerConfig::Protobuf(config) => config.schema_requirement(),
         }
     }
 }
@@ -405,6 +498,8 @@ pub enum Serializer {
     Avro(AvroSerializer),
     /// Uses a `GelfSerializer` for serialization.
     Gelf(GelfSerializer),
+    /// Uses a `CborSerializer` for serialization.
+    Cbor(CborSerializer),
     /// Uses a `JsonSerializer` for serialization.
     Json(JsonSerializer),
     /// Uses a `LogfmtSerializer` for serialization.
@@ -419,6 +514,8 @@ pub enum Serializer {
     Text(TextSerializer),
     /// Uses a `SyslogSerializer` for serialization.
     Syslog(SyslogSerializer),
+    /// Uses a `ProtobufSerializer` for serialization.
+    Protobuf(ProtobufSerializer),
 }
 
 impl Serializer {
@@ -427,11 +524,13 @@ impl Serializer {
         match self {
             Serializer::Json(_) | Serializer::NativeJson(_) | Serializer::Gelf(_) => true,
             Serializer::Avro(_)
+            | Serializer::Cbor(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::RawMessage(_)
-            | Serializer::Syslog(_) => false,
+            | Serializer::Syslog(_)
+            | Serializer::Protobuf(_) => false,
         }
     }
 
@@ -447,11 +546,13 @@ impl Serializer {
             Serializer::Json(serializer) => serializer.to_json_value(event),
             Serializer::NativeJson(serializer) => serializer.to_json_value(event),
             Serializer::Avro(_)
+            | Serializer::Cbor(_)
             | Serializer::Logfmt(_)
             | Serializer::Text(_)
             | Serializer::Native(_)
             | Serializer::RawMessage(_)
-            | Serializer::Syslog(_) => {
+            | Serializer::Syslog(_)
+            | Serializer::Protobuf(_) => {
                 panic!("Serializer does not support JSON")
             }
         }
@@ -470,6 +571,12 @@ impl From<GelfSerializer> for Serializer {
     }
 }
 
+impl From<CborSerializer> for Serializer {
+    fn from(serializer: CborSerializer) -> Self {
+        Self::Cbor(serializer)
+    }
+}
+
 impl From<JsonSerializer> for Serializer {
     fn from(serializer: JsonSerializer) -> Self {
         Self::Json(serializer)
@@ -512,6 +619,12 @@ impl From<SyslogSerializer> for Serializer {
     }
 }
 
+impl From<ProtobufSerializer> for Serializer {
+    fn from(serializer: ProtobufSerializer) -> Self {
+        Self::Protobuf(serializer)
+    }
+}
+
 impl tokio_util::codec::Encoder<Event> for Serializer {
     type Error = vector_common::Error;
 
@@ -519,6 +632,7 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
         match self {
             Serializer::Avro(serializer) => serializer.encode(event, buffer),
             Serializer::Gelf(serializer) => serializer.encode(event, buffer),
+            Serializer::Cbor(serializer) => serializer.encode(event, buffer),
             Serializer::Json(serializer) => serializer.encode(event, buffer),
             Serializer::Logfmt(serializer) => serializer.encode(event, buffer),
             Serializer::Native(serializer) => serializer.encode(event, buffer),
@@ -526,6 +640,49 @@ impl tokio_util::codec::Encoder<Event> for Serializer {
             Serializer::RawMessage(serializer) => serializer.encode(event, buffer),
             Serializer::Text(serializer) => serializer.encode(event, buffer),
             Serializer::Syslog(serializer) => serializer.encode(event, buffer),
+            Serializer::Protobuf(serializer) => serializer.encode(event, buffer),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_codec_flattens_with_no_options() {
+        let config: SerializerConfig = serde_json::from_str(r#"{"codec": "json"}"#).unwrap();
+        let SerializerConfig::Json(json) = config else {
+            panic!("expected SerializerConfig::Json");
+        };
+        assert_eq!(json, JsonSerializerConfig::default());
+    }
+
+    #[test]
+    fn json_codec_flattens_sibling_pretty_false() {
+        let config: SerializerConfig =
+            serde_json::from_str(r#"{"codec": "json", "pretty": false}"#).unwrap();
+        let SerializerConfig::Json(json) = config else {
+            panic!("expected SerializerConfig::Json");
+        };
+        assert_eq!(json, JsonSerializerConfig::default());
+    }
+
+    #[test]
+    fn json_codec_flattens_sibling_pretty_true() {
+        let config: SerializerConfig =
+            serde_json::from_str(r#"{"codec": "json", "pretty": true}"#).unwrap();
+        let SerializerConfig::Json(json) = config else {
+            panic!("expected SerializerConfig::Json");
+        };
+        assert!(json.pretty);
+        assert!(!json.sort_keys);
+
+        let round_tripped: SerializerConfig =
+            serde_json::from_str(&serde_json::to_string(&config).unwrap()).unwrap();
+        let SerializerConfig::Json(round_tripped) = round_tripped else {
+            panic!("expected SerializerConfig::Json");
+        };
+        assert_eq!(json, round_tripped);
+    }
+}
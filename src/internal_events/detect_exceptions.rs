@@ -1,11 +1,133 @@
-use metrics::counter;
+use metrics::{counter, gauge};
 use vector_core::internal_event::InternalEvent;
 
+use crate::emit;
+use vector_common::internal_event::{ComponentEventsDropped, UNINTENTIONAL};
+
+#[derive(Debug)]
+pub struct DetectExceptionsEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for DetectExceptionsEventsReceived {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+    }
+}
+
+/// Emitted when a group of buffered lines is combined into a single multiline event.
+#[derive(Debug)]
+pub struct DetectExceptionsEventsCombined {
+    pub lines: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for DetectExceptionsEventsCombined {
+    fn emit(self) {
+        trace!(
+            message = "Multiline event combined.",
+            lines = %self.lines,
+            byte_size = %self.byte_size,
+        );
+        counter!("detect_exceptions_combined_events_total", 1);
+        counter!("detect_exceptions_combined_lines_total", self.lines as u64);
+        counter!(
+            "detect_exceptions_combined_bytes_total",
+            self.byte_size as u64
+        );
+    }
+}
+
+/// Emitted the moment the detector transitions from no trace into the start of a new one, i.e.
+/// once per distinct exception/stack trace found, regardless of how many lines it spans.
+#[derive(Debug)]
+pub struct DetectExceptionsTraceDetected;
+
+impl InternalEvent for DetectExceptionsTraceDetected {
+    fn emit(self) {
+        trace!(message = "Exception trace detected.");
+        counter!("detect_exceptions_traces_detected_total", 1);
+    }
+}
+
+/// Number of `TraceAccumulator`s currently live, i.e. one per distinct `group_by` discriminant
+/// with a buffer that hasn't yet been reaped.
 #[derive(Debug)]
-pub struct DetectExceptionsStaleEventFlushed;
+pub struct DetectExceptionsAccumulatorsActive {
+    pub count: usize,
+}
+
+impl InternalEvent for DetectExceptionsAccumulatorsActive {
+    fn emit(self) {
+        gauge!("detect_exceptions_active_accumulators", self.count as f64);
+    }
+}
+
+/// Why a `TraceAccumulator`'s buffer was force-flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DetectExceptionsFlushReason {
+    /// The group was idle for longer than `expire_after_ms` and was reaped entirely.
+    Expired,
+    /// The per-group `max_bytes` limit, or the `total_max_bytes` budget across all groups, was hit.
+    MaxBytes,
+    /// The per-group `max_lines` limit was hit.
+    MaxLines,
+    /// The buffer was idle for longer than `multiline_flush_interval_ms`.
+    MultilineInterval,
+    /// The input stream ended and all remaining buffers were drained.
+    StreamEnd,
+    /// The group was evicted to keep the number of live streams within `max_streams`.
+    MaxStreams,
+}
+
+impl DetectExceptionsFlushReason {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Expired => "expired",
+            Self::MaxBytes => "max_bytes",
+            Self::MaxLines => "max_lines",
+            Self::MultilineInterval => "multiline_interval",
+            Self::StreamEnd => "stream_end",
+            Self::MaxStreams => "max_streams",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct DetectExceptionsBufferFlushed {
+    pub reason: DetectExceptionsFlushReason,
+}
+
+impl InternalEvent for DetectExceptionsBufferFlushed {
+    fn emit(self) {
+        counter!("detect_exceptions_flushed_total", 1, "reason" => self.reason.as_str());
+    }
+}
+
+/// Emitted when a multiline buffer is force-flushed (and thus split across two logical events)
+/// because `max_bytes`/`max_lines` was hit, or its stream was evicted to stay within
+/// `max_streams`, before the trace naturally ended.
+#[derive(Debug)]
+pub struct DetectExceptionsBufferTruncated {
+    pub reason: DetectExceptionsFlushReason,
+    pub count: usize,
+}
 
-impl InternalEvent for DetectExceptionsStaleEventFlushed {
+impl InternalEvent for DetectExceptionsBufferTruncated {
     fn emit(self) {
-        counter!("detect_exceptions_stale_flushed_total", 1);
+        emit!(ComponentEventsDropped::<UNINTENTIONAL> {
+            count: self.count,
+            reason: self.reason.as_str(),
+        });
     }
 }
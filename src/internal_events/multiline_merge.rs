@@ -0,0 +1,84 @@
+use metrics::counter;
+use vector_core::internal_event::InternalEvent;
+
+#[derive(Debug)]
+pub struct MultilineMergeEventsReceived {
+    pub count: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for MultilineMergeEventsReceived {
+    fn emit(self) {
+        trace!(
+            message = "Events received.",
+            count = %self.count,
+            byte_size = %self.byte_size,
+        );
+        counter!("component_received_events_total", self.count as u64);
+        counter!(
+            "component_received_event_bytes_total",
+            self.byte_size as u64
+        );
+    }
+}
+
+/// Emitted when a buffer of continuation lines is joined into a single merged event.
+#[derive(Debug)]
+pub struct MultilineMergeEventsCombined {
+    pub lines: usize,
+    pub byte_size: usize,
+}
+
+impl InternalEvent for MultilineMergeEventsCombined {
+    fn emit(self) {
+        trace!(
+            message = "Multiline event merged.",
+            lines = %self.lines,
+            byte_size = %self.byte_size,
+        );
+        counter!("multiline_merge_combined_events_total", 1);
+        counter!("multiline_merge_combined_lines_total", self.lines as u64);
+        counter!(
+            "multiline_merge_combined_bytes_total",
+            self.byte_size as u64
+        );
+    }
+}
+
+/// Why a buffer was flushed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MultilineMergeFlushReason {
+    /// A start line arrived, ending the previous buffer.
+    StartLine,
+    /// The buffer was idle for longer than `timeout_ms`.
+    Timeout,
+    /// The per-buffer `max_lines` limit was hit.
+    MaxLines,
+    /// The per-buffer `max_bytes` limit was hit.
+    MaxBytes,
+    /// The input stream ended and the remaining buffer was drained.
+    StreamEnd,
+}
+
+impl MultilineMergeFlushReason {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::StartLine => "start_line",
+            Self::Timeout => "timeout",
+            Self::MaxLines => "max_lines",
+            Self::MaxBytes => "max_bytes",
+            Self::StreamEnd => "stream_end",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct MultilineMergeBufferFlushed {
+    pub reason: MultilineMergeFlushReason,
+}
+
+impl InternalEvent for MultilineMergeBufferFlushed {
+    fn emit(self) {
+        counter!("multiline_merge_flushed_total", 1, "reason" => self.reason.as_str());
+    }
+}
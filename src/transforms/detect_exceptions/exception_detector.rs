@@ -1,44 +1,82 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::usize;
 use chrono::{DateTime, Utc};
-use regex::Regex;
+use regex::RegexSet;
 use crate::{
     config::log_schema, event::LogEvent, event::Value,
-    internal_events::detect_exceptions::DetectExceptionsStaleEventFlushed,
+    internal_events::detect_exceptions::{DetectExceptionsBufferFlushed, DetectExceptionsBufferTruncated, DetectExceptionsEventsCombined, DetectExceptionsFlushReason, DetectExceptionsTraceDetected},
     transforms::detect_exceptions::*,
 };
 
-#[derive(Debug, Clone)]
-pub struct RuleTarget {
-    regex: Regex,
-    to_state: ExceptionState,
+/// The compiled transitions out of a single state: a `RegexSet` over every candidate pattern plus
+/// the target state for each, in the same order as the set's pattern indices. Matching the whole
+/// set in one scan is much cheaper than testing each pattern individually once many languages
+/// (and thus many candidate patterns per state) are enabled.
+pub struct CompiledState {
+    set: RegexSet,
+    /// `targets[i]` is the state to transition to when `set`'s pattern `i` matches. Earlier
+    /// indices take priority, preserving the original rule-order-wins semantics.
+    targets: Vec<ExceptionState>,
 }
-type StateMachine = HashMap<ExceptionState, Vec<RuleTarget>>;
+type StateMachine = HashMap<ExceptionState, CompiledState>;
 
 use rules::*;
 
+/// Converts a config-level [`CustomRule`] into the same [`Rule`] shape the built-in grammars use,
+/// resolving its state names via [`ExceptionState::from_custom_name`].
+fn custom_rule_to_rule(custom: &CustomRule) -> Rule<'_> {
+    Rule {
+        from_states: custom
+            .from_states
+            .iter()
+            .map(|s| ExceptionState::from_custom_name(s))
+            .collect(),
+        pattern: custom.pattern.as_str(),
+        to_state: ExceptionState::from_custom_name(&custom.to_state),
+    }
+}
+
 pub fn get_state_machines(
     mut langs: Vec<ProgrammingLanguages>,
-) -> HashMap<ExceptionState, Vec<RuleTarget>> {
-    let mut rules: HashMap<ExceptionState, Vec<RuleTarget>> = HashMap::new();
+    custom_rules: &[CustomRule],
+) -> StateMachine {
+    let mut patterns: HashMap<ExceptionState, (Vec<&str>, Vec<ExceptionState>)> = HashMap::new();
     let rules_by_lang = rules_by_lang();
     if langs.is_empty() {
         langs = vec![ProgrammingLanguages::All];
     }
-    for lang in langs {
-        let rule_config = rules_by_lang.get(&lang).unwrap();
-        for rc in rule_config {
-            let t = RuleTarget {
-                regex: Regex::new(rc.pattern).unwrap(),
-                to_state: rc.to_state,
-            };
+
+    let custom: Vec<Rule> = custom_rules.iter().map(custom_rule_to_rule).collect();
+
+    for lang in &langs {
+        for rc in rules_by_lang.get(lang).unwrap() {
             for s in &rc.from_states {
-                let entry = rules.entry(*s).or_insert(vec![]);
-                entry.append(&mut vec![t.clone()]);
+                let entry = patterns.entry(s.clone()).or_insert_with(|| (vec![], vec![]));
+                entry.0.push(rc.pattern);
+                entry.1.push(rc.to_state.clone());
             }
         }
     }
-    rules
+    for rc in &custom {
+        for s in &rc.from_states {
+            let entry = patterns.entry(s.clone()).or_insert_with(|| (vec![], vec![]));
+            entry.0.push(rc.pattern);
+            entry.1.push(rc.to_state.clone());
+        }
+    }
+
+    patterns
+        .into_iter()
+        .map(|(state, (set_patterns, targets))| {
+            (
+                state,
+                CompiledState {
+                    set: RegexSet::new(set_patterns).unwrap(),
+                    targets,
+                },
+            )
+        })
+        .collect()
 }
 
 #[derive(Debug, PartialEq, Eq, Hash, Clone)]
@@ -49,40 +87,41 @@ pub enum DetectionStatus {
     EndTrace,
 }
 
-pub struct TraceAccumulator {
-    max_bytes: usize,
-    max_lines: usize,
-    multiline_flush_interval: Duration,
+/// Per-stream state: its own detector, in-flight buffer, and buffer start time. Kept separate per
+/// `stream_identity_key` value so interleaved lines from different sources (e.g. different
+/// containers sharing a `group_by` discriminant) don't corrupt each other's multiline detection.
+struct StreamBuffer {
     first_event: LogEvent,
     buffer_size: usize,
     detector: ExceptionDetector,
-    pub buffer_start_time: DateTime<Utc>,
-    pub accumulated_messages: Vec<String>,
+    buffer_start_time: DateTime<Utc>,
+    accumulated_messages: Vec<String>,
 }
 
-impl TraceAccumulator {
-    pub fn new(
-        languages: Vec<ProgrammingLanguages>,
-        multiline_flush_interval: Duration,
-        max_bytes: usize,
-        max_lines: usize,
-    ) -> TraceAccumulator {
-        TraceAccumulator {
+impl StreamBuffer {
+    fn new(languages: Vec<ProgrammingLanguages>, custom_rules: &[CustomRule]) -> StreamBuffer {
+        StreamBuffer {
             buffer_size: 0,
-            max_bytes,
-            max_lines,
-            multiline_flush_interval,
             first_event: LogEvent::default(),
             buffer_start_time: Utc::now(),
             accumulated_messages: vec![],
             detector: ExceptionDetector {
-                state_machine: get_state_machines(languages),
+                state_machine: get_state_machines(languages, custom_rules),
                 current_state: ExceptionState::StartState,
             },
         }
     }
 
-    pub fn push(&mut self, le: &LogEvent, output: &mut Vec<Event>) {
+    #[allow(clippy::too_many_arguments)]
+    fn push(
+        &mut self,
+        le: &LogEvent,
+        max_bytes: usize,
+        max_lines: usize,
+        join_separator: &str,
+        annotate_combined: bool,
+        output: &mut Vec<Event>,
+    ) {
         let mut detection_status = DetectionStatus::NoTrace;
         let message = le.get(log_schema().message_key_target_path().unwrap());
         let message_copy = message.clone();
@@ -91,25 +130,53 @@ impl TraceAccumulator {
             None => self.detector.reset(),
             Some(v) => {
                 let s = v.to_string_lossy();
-                if self.max_bytes > 0 && self.buffer_size + s.len() > self.max_bytes {
-                    self.force_flush(output);
+                if max_bytes > 0 && self.buffer_size + s.len() > max_bytes {
+                    self.force_flush(
+                        DetectExceptionsFlushReason::MaxBytes,
+                        join_separator,
+                        annotate_combined,
+                        output,
+                    );
                 }
+                let was_idle = self.detector.current_state == ExceptionState::StartState;
                 detection_status = self.detector.update(&s.to_string());
+                // A trace opened from the idle state reports `InsideTrace`; `StartTrace` only
+                // marks a trace that directly follows another one.
+                if detection_status == DetectionStatus::StartTrace
+                    || (was_idle && detection_status == DetectionStatus::InsideTrace)
+                {
+                    emit!(DetectExceptionsTraceDetected);
+                }
             }
         }
 
-        self.update_buffer(detection_status, message_copy, le, output);
+        self.update_buffer(
+            detection_status,
+            message_copy,
+            le,
+            join_separator,
+            annotate_combined,
+            output,
+        );
 
-        if self.max_lines > 0 && self.accumulated_messages.len() == self.max_lines {
-            self.force_flush(output);
+        if max_lines > 0 && self.accumulated_messages.len() == max_lines {
+            self.force_flush(
+                DetectExceptionsFlushReason::MaxLines,
+                join_separator,
+                annotate_combined,
+                output,
+            );
         }
     }
 
-    pub fn update_buffer(
+    #[allow(clippy::too_many_arguments)]
+    fn update_buffer(
         &mut self,
         detection_status: DetectionStatus,
         message: Option<&Value>,
         le: &LogEvent,
+        join_separator: &str,
+        annotate_combined: bool,
         output: &mut Vec<Event>,
     ) {
         let trigger_emit = match detection_status {
@@ -126,21 +193,21 @@ impl TraceAccumulator {
             DetectionStatus::InsideTrace => self.add(le, message),
             DetectionStatus::EndTrace => {
                 self.add(le, message);
-                self.flush(output);
+                self.flush(join_separator, annotate_combined, output);
             }
             DetectionStatus::NoTrace => {
-                self.flush(output);
+                self.flush(join_separator, annotate_combined, output);
                 self.add(le, message);
-                self.flush(output);
+                self.flush(join_separator, annotate_combined, output);
             }
             DetectionStatus::StartTrace => {
-                self.flush(output);
+                self.flush(join_separator, annotate_combined, output);
                 self.add(le, message);
             }
         }
     }
 
-    pub fn add(&mut self, le: &LogEvent, message: Option<&Value>) {
+    fn add(&mut self, le: &LogEvent, message: Option<&Value>) {
         if self.accumulated_messages.is_empty() {
             self.first_event = le.to_owned();
             self.buffer_start_time = Utc::now();
@@ -153,16 +220,31 @@ impl TraceAccumulator {
         }
     }
 
-    pub fn flush(&mut self, output: &mut Vec<Event>) {
+    /// Flushes the buffer. When more than one line is buffered, the lines are joined with
+    /// `join_separator` into `first_event`, and, if `annotate_combined` is set, `first_event` is
+    /// tagged with `exception.combined`, `exception.line_count`, and `exception.byte_size` so
+    /// downstream consumers can tell a combined trace from an ordinary log line.
+    fn flush(&mut self, join_separator: &str, annotate_combined: bool, output: &mut Vec<Event>) {
         match self.accumulated_messages.len() {
             0 => return,
             1 => {
                 output.push(Event::Log(self.first_event.to_owned()));
             }
-            _ => {
+            n => {
+                let joined = self.accumulated_messages.join(join_separator);
+                emit!(DetectExceptionsEventsCombined {
+                    lines: n,
+                    byte_size: joined.len(),
+                });
+                if annotate_combined {
+                    self.first_event.insert("exception.combined", true);
+                    self.first_event.insert("exception.line_count", n as i64);
+                    self.first_event
+                        .insert("exception.byte_size", joined.len() as i64);
+                }
                 self.first_event.insert(
                     log_schema().message_key_target_path().unwrap(),
-                    self.accumulated_messages.join("\n"),
+                    joined,
                 );
                 output.push(Event::Log(self.first_event.clone()));
             }
@@ -172,25 +254,232 @@ impl TraceAccumulator {
         self.buffer_size = 0;
     }
 
-    pub fn force_flush(&mut self, output: &mut Vec<Event>) {
-        self.flush(output);
+    fn buffer_size(&self) -> usize {
+        self.buffer_size
+    }
+
+    /// Flushes whatever is currently buffered and resets the detector state, emitting a
+    /// `DetectExceptionsBufferFlushed` for `reason`. If the buffer was non-empty and `reason` is
+    /// `MaxBytes`/`MaxLines`/`MaxStreams`, this also reports the truncation as an unintentional
+    /// event drop, since the in-progress trace is being split rather than allowed to complete
+    /// naturally.
+    fn force_flush(
+        &mut self,
+        reason: DetectExceptionsFlushReason,
+        join_separator: &str,
+        annotate_combined: bool,
+        output: &mut Vec<Event>,
+    ) {
+        let had_buffered = !self.accumulated_messages.is_empty();
+        self.flush(join_separator, annotate_combined, output);
         self.detector.reset();
+        if had_buffered {
+            emit!(DetectExceptionsBufferFlushed { reason });
+            if matches!(
+                reason,
+                DetectExceptionsFlushReason::MaxBytes
+                    | DetectExceptionsFlushReason::MaxLines
+                    | DetectExceptionsFlushReason::MaxStreams
+            ) {
+                emit!(DetectExceptionsBufferTruncated { reason, count: 1 });
+            }
+        }
     }
 
-    pub fn flush_stale_into(&mut self, now: DateTime<Utc>, output: &mut Vec<Event>) {
+    #[allow(clippy::too_many_arguments)]
+    fn flush_stale_into(
+        &mut self,
+        now: DateTime<Utc>,
+        multiline_flush_interval: Duration,
+        join_separator: &str,
+        annotate_combined: bool,
+        output: &mut Vec<Event>,
+    ) {
         if now.timestamp_millis() - self.buffer_start_time.timestamp_millis()
-            > self
-                .multiline_flush_interval
-                .as_millis()
-                .try_into()
-                .unwrap()
+            > multiline_flush_interval.as_millis().try_into().unwrap()
         {
-            emit!(DetectExceptionsStaleEventFlushed);
-            self.force_flush(output);
+            self.force_flush(
+                DetectExceptionsFlushReason::MultilineInterval,
+                join_separator,
+                annotate_combined,
+                output,
+            );
         }
     }
 }
 
+/// Demultiplexes events into one [`StreamBuffer`] per distinct value of `stream_identity_key`
+/// (events missing the key all fall into a shared default stream), so each source's partial
+/// trace is tracked independently. Because a high-cardinality key can otherwise grow the map
+/// without bound, `max_streams` force-flushes and evicts the least-recently-updated stream
+/// whenever it would be exceeded.
+pub struct TraceAccumulator {
+    max_bytes: usize,
+    max_lines: usize,
+    multiline_flush_interval: Duration,
+    languages: Vec<ProgrammingLanguages>,
+    custom_rules: Vec<CustomRule>,
+    join_separator: String,
+    annotate_combined: bool,
+    stream_identity_key: Option<String>,
+    max_streams: usize,
+    streams: HashMap<String, StreamBuffer>,
+    /// Stream keys ordered from least- (front) to most- (back) recently updated, used to pick
+    /// eviction candidates when `max_streams` is exceeded.
+    stream_order: VecDeque<String>,
+    /// Most recent activity across every stream, used by the owning `DetectExceptions` to decide
+    /// when this accumulator as a whole has gone idle long enough to be reaped.
+    pub last_activity: DateTime<Utc>,
+}
+
+impl TraceAccumulator {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        languages: Vec<ProgrammingLanguages>,
+        custom_rules: Vec<CustomRule>,
+        multiline_flush_interval: Duration,
+        max_bytes: usize,
+        max_lines: usize,
+        join_separator: String,
+        annotate_combined: bool,
+        stream_identity_key: Option<String>,
+        max_streams: usize,
+    ) -> TraceAccumulator {
+        TraceAccumulator {
+            max_bytes,
+            max_lines,
+            multiline_flush_interval,
+            languages,
+            custom_rules,
+            join_separator,
+            annotate_combined,
+            stream_identity_key,
+            max_streams,
+            streams: HashMap::new(),
+            stream_order: VecDeque::new(),
+            last_activity: Utc::now(),
+        }
+    }
+
+    /// The stream this event belongs to: the value of `stream_identity_key` if set and present on
+    /// the event, otherwise the default stream shared by every event without one.
+    fn stream_key(&self, le: &LogEvent) -> String {
+        match &self.stream_identity_key {
+            None => String::new(),
+            Some(field) => le
+                .get(field.as_str())
+                .map(|v| v.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+        }
+    }
+
+    fn touch_stream(&mut self, key: &str) {
+        if let Some(index) = self.stream_order.iter().position(|k| k == key) {
+            self.stream_order.remove(index);
+        }
+        self.stream_order.push_back(key.to_owned());
+    }
+
+    /// Force-flushes and evicts the least-recently-updated stream until `streams` is back within
+    /// `max_streams`, skipping `keep` (the stream this push is about to land in).
+    fn enforce_max_streams(&mut self, keep: &str, output: &mut Vec<Event>) {
+        if self.max_streams == 0 {
+            return;
+        }
+
+        while self.streams.len() > self.max_streams {
+            let Some(index) = self.stream_order.iter().position(|k| k != keep) else {
+                break;
+            };
+            let key = self.stream_order.remove(index).unwrap();
+            if let Some(mut stream) = self.streams.remove(&key) {
+                debug!("evicting stream {:?} to stay within max_streams", key);
+                stream.force_flush(
+                    DetectExceptionsFlushReason::MaxStreams,
+                    &self.join_separator,
+                    self.annotate_combined,
+                    output,
+                );
+            }
+        }
+    }
+
+    pub fn push(&mut self, le: &LogEvent, output: &mut Vec<Event>) {
+        let key = self.stream_key(le);
+        self.last_activity = Utc::now();
+
+        if !self.streams.contains_key(&key) {
+            self.streams.insert(
+                key.clone(),
+                StreamBuffer::new(self.languages.clone(), &self.custom_rules),
+            );
+        }
+        self.touch_stream(&key);
+        self.enforce_max_streams(&key, output);
+
+        let stream = self.streams.get_mut(&key).unwrap();
+        stream.push(
+            le,
+            self.max_bytes,
+            self.max_lines,
+            &self.join_separator,
+            self.annotate_combined,
+            output,
+        );
+    }
+
+    /// Current number of bytes buffered across every stream's in-flight partial trace.
+    pub fn buffer_size(&self) -> usize {
+        self.streams.values().map(StreamBuffer::buffer_size).sum()
+    }
+
+    /// Whether every stream has been fully flushed, i.e. there's nothing left buffered anywhere.
+    pub fn is_empty(&self) -> bool {
+        self.streams
+            .values()
+            .all(|stream| stream.accumulated_messages.is_empty())
+    }
+
+    /// Force-flushes every stream, emitting `DetectExceptionsBufferFlushed`/`Truncated` per
+    /// stream that had something buffered, then drops all stream state.
+    pub fn force_flush(&mut self, reason: DetectExceptionsFlushReason, output: &mut Vec<Event>) {
+        for stream in self.streams.values_mut() {
+            stream.force_flush(reason, &self.join_separator, self.annotate_combined, output);
+        }
+        self.streams.clear();
+        self.stream_order.clear();
+    }
+
+    /// Flushes whatever is currently buffered, stream by stream, without resetting detector state
+    /// or evicting streams. Used when the input ends and everything must be drained as-is.
+    pub fn flush(&mut self, output: &mut Vec<Event>) {
+        for stream in self.streams.values_mut() {
+            stream.flush(&self.join_separator, self.annotate_combined, output);
+        }
+    }
+
+    /// Force-flushes any stream idle longer than `multiline_flush_interval`, then drops streams
+    /// that are now empty so the map doesn't accumulate dead entries from one-off keys.
+    pub fn flush_stale_into(&mut self, now: DateTime<Utc>, output: &mut Vec<Event>) {
+        for stream in self.streams.values_mut() {
+            stream.flush_stale_into(
+                now,
+                self.multiline_flush_interval,
+                &self.join_separator,
+                self.annotate_combined,
+                output,
+            );
+        }
+        self.streams.retain(|key, stream| {
+            let keep = !stream.accumulated_messages.is_empty();
+            if !keep {
+                self.stream_order.retain(|k| k != key);
+            }
+            keep
+        });
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ExceptionDetectorConfig {}
 
@@ -215,15 +504,17 @@ impl ExceptionDetector {
     }
 
     pub fn transition(&mut self, message: &String) -> bool {
-        let transitions = self.state_machine.get(&(self.current_state)).unwrap();
-        for transition in transitions {
-            if transition.regex.is_match(message.as_ref()) {
-                self.current_state = transition.to_state.clone();
-                return true;
+        let compiled = self.state_machine.get(&(self.current_state)).unwrap();
+        match compiled.set.matches(message.as_ref()).iter().min() {
+            Some(index) => {
+                self.current_state = compiled.targets[index].clone();
+                true
+            }
+            None => {
+                self.current_state = ExceptionState::StartState;
+                false
             }
         }
-        self.current_state = ExceptionState::StartState;
-        false
     }
 
     pub fn reset(&mut self) {
@@ -261,7 +552,7 @@ mod exception_detector_tests {
     fn check_exception(line: &str, detects_end: bool) {
         let lines = split(line);
         let mut detector = ExceptionDetector {
-            state_machine: get_state_machines(default_programming_languages()),
+            state_machine: get_state_machines(default_programming_languages(), &[]),
             current_state: ExceptionState::StartState,
         };
         let after_exc = if detects_end { EndTrace } else { InsideTrace };
@@ -7,13 +7,17 @@ use serde_with::serde_as;
 
 use crate::{
     config::{DataType, Input, OutputId, TransformOutput, TransformConfig, TransformContext, TransformDescription},
-    event::{discriminant::Discriminant, Event},
+    event::{discriminant::Discriminant, Event, Value},
+    internal_events::detect_exceptions::{
+        DetectExceptionsAccumulatorsActive, DetectExceptionsBufferFlushed,
+        DetectExceptionsEventsReceived, DetectExceptionsFlushReason,
+    },
     schema,
     transforms::{TaskTransform, Transform}
 };
 use async_stream::stream;
 use futures::{stream, Stream, StreamExt};
-use std::{collections::HashMap, pin::Pin, time::Duration};
+use std::{collections::HashMap, collections::VecDeque, pin::Pin, time::Duration};
 use vector_config::configurable_component;
 use vector_core::config::LogNamespace;
 use vector_core::config::clone_input_definitions;
@@ -56,6 +60,78 @@ pub enum ProgrammingLanguages {
     All,
 }
 
+/// A log severity level, ordered from least to most severe.
+#[configurable_component]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    /// Trace-level diagnostics.
+    Trace,
+
+    /// Debug-level diagnostics.
+    Debug,
+
+    /// Informational messages.
+    Info,
+
+    /// Warnings.
+    Warn,
+
+    /// Errors.
+    Error,
+
+    /// Fatal/critical errors.
+    Fatal,
+}
+
+impl Severity {
+    /// Parses a severity out of a field value, supporting both numeric levels (`0` = `trace`
+    /// through `5` = `fatal`) and well-known textual names (including common aliases such as
+    /// `warning` and `crit`). Returns `None` if `value` doesn't match either form.
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Integer(n) => match n {
+                0 => Some(Self::Trace),
+                1 => Some(Self::Debug),
+                2 => Some(Self::Info),
+                3 => Some(Self::Warn),
+                4 => Some(Self::Error),
+                5 => Some(Self::Fatal),
+                _ => None,
+            },
+            Value::Bytes(_) => match value.to_string_lossy().to_ascii_lowercase().as_str() {
+                "trace" => Some(Self::Trace),
+                "debug" => Some(Self::Debug),
+                "info" | "informational" => Some(Self::Info),
+                "warn" | "warning" => Some(Self::Warn),
+                "error" | "err" => Some(Self::Error),
+                "fatal" | "critical" | "crit" => Some(Self::Fatal),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+}
+
+/// A single state-transition rule in a user-defined exception grammar, merged into the state
+/// machine built from `languages`. Mirrors the shape of the built-in, hardcoded rule tables, but
+/// with states named by the user instead of a fixed enum.
+#[configurable_component]
+#[derive(Debug, Clone)]
+pub struct CustomRule {
+    /// States from which this rule can fire. The name `StartState` refers to the shared idle/
+    /// reset state used by every grammar, built-in and custom alike; any other name is a state
+    /// private to this set of custom rules.
+    pub from_states: Vec<String>,
+
+    /// Regex tested against each line while the detector is in one of `from_states`.
+    pub pattern: String,
+
+    /// State entered when `pattern` matches. Use `StartState` to route back to the shared idle
+    /// state, ending the custom trace.
+    pub to_state: String,
+}
+
 /// Configuration for the `detect_exceptions` transform.
 #[serde_as]
 #[configurable_component(transform("detect_exceptions"))]
@@ -75,6 +151,12 @@ pub struct DetectExceptionsConfig {
     #[serde(default = "default_programming_languages")]
     pub languages: Vec<ProgrammingLanguages>,
 
+    /// User-defined exception-detection rules, merged into the state machine built from
+    /// `languages`. Lets you detect stack trace formats that aren't built in — a bespoke
+    /// application logger, or a language like .NET/C# or Elixir — without recompiling Vector.
+    #[serde(default)]
+    pub custom_rules: Vec<CustomRule>,
+
     /// The maximum period of time to wait after the last event is received, in milliseconds, before
     /// a combined event should be considered complete.
     #[serde(default = "default_expire_after_ms")]
@@ -109,18 +191,80 @@ pub struct DetectExceptionsConfig {
     /// Maximum number of lines to flush (0 means no limit). Default: 1000.
     #[serde(default = "default_max_lines_num")]
     pub max_lines: usize,
+
+    /// Separator inserted between lines when multiple are combined into a single event.
+    #[serde(default = "default_join_separator")]
+    pub join_separator: String,
+
+    /// When `true`, an event produced by combining more than one line is annotated with
+    /// `exception.combined` (always `true` on such events), `exception.line_count`, and
+    /// `exception.byte_size`, so downstream consumers can distinguish a combined trace from an
+    /// ordinary log line. Default: `false`.
+    #[serde(default)]
+    pub annotate_combined_events: bool,
+
+    /// Maximum number of bytes buffered across *all* groups combined (0 means no limit).
+    /// Default: 0.
+    ///
+    /// Unlike `max_bytes`, which bounds a single group's buffer, this bounds the sum across every
+    /// `group_by` discriminant. When a high-cardinality `group_by` would otherwise let the number
+    /// of in-flight accumulators grow without bound, exceeding this budget force-flushes the
+    /// least-recently-updated groups until the total is back under budget.
+    #[serde(default = "default_total_max_bytes_size")]
+    pub total_max_bytes: usize,
+
+    /// Field containing each event's severity/level.
+    ///
+    /// When set, only lines at or above `min_severity` participate in exception detection and
+    /// multiline grouping; lines below the threshold are forwarded immediately and unchanged,
+    /// bypassing the accumulator entirely. Lines whose `severity_field` is missing or holds an
+    /// unrecognized level also participate. When unset (the default), every line runs through the
+    /// language matchers, matching the transform's behavior before severity gating existed.
+    #[serde(default)]
+    pub severity_field: Option<String>,
+
+    /// The minimum severity (inclusive) a line's `severity_field` value must meet to participate
+    /// in multiline grouping. Has no effect unless `severity_field` is set.
+    #[serde(default = "default_min_severity")]
+    pub min_severity: Severity,
+
+    /// Field identifying the individual stream an event belongs to, within its `group_by` group.
+    ///
+    /// When set, each distinct value of this field (within a group) gets its own independent
+    /// multiline buffer and detector, so interleaved lines from different sources sharing the
+    /// same `group_by` discriminant don't corrupt each other's exception detection. When unset
+    /// (the default), every event in a group shares a single buffer, matching the transform's
+    /// behavior before per-stream demultiplexing existed.
+    #[serde(default)]
+    pub stream_identity_key: Option<String>,
+
+    /// Maximum number of concurrent streams to track per group (0 means no limit). Default: 0.
+    ///
+    /// Has no effect unless `stream_identity_key` is set. When a high-cardinality key would
+    /// otherwise let the number of per-group streams grow without bound, exceeding this limit
+    /// force-flushes the least-recently-updated stream in that group.
+    #[serde(default = "default_max_streams")]
+    pub max_streams: usize,
 }
 
 impl Default for DetectExceptionsConfig {
     fn default() -> Self {
         Self {
             languages: default_programming_languages(),
+            custom_rules: vec![],
             expire_after_ms: default_expire_after_ms(),
             flush_period_ms: default_flush_period_ms(),
             multiline_flush_interval_ms: default_multiline_flush_interval_ms(),
             max_bytes: default_max_bytes_size(),
             max_lines: default_max_lines_num(),
+            join_separator: default_join_separator(),
+            annotate_combined_events: false,
+            total_max_bytes: default_total_max_bytes_size(),
             group_by: vec![],
+            severity_field: None,
+            min_severity: default_min_severity(),
+            stream_identity_key: None,
+            max_streams: default_max_streams(),
         }
     }
 }
@@ -149,6 +293,22 @@ const fn default_max_lines_num() -> usize {
     1000
 }
 
+fn default_join_separator() -> String {
+    "\n".to_string()
+}
+
+const fn default_total_max_bytes_size() -> usize {
+    0
+}
+
+const fn default_min_severity() -> Severity {
+    Severity::Warn
+}
+
+const fn default_max_streams() -> usize {
+    0
+}
+
 impl_generate_config_from_default!(DetectExceptionsConfig);
 inventory::submit! {
     TransformDescription::new::<DetectExceptionsConfig>("detect_exceptions", "detect_exceptions", "detect_exceptions", "detect_exceptions")
@@ -180,13 +340,26 @@ impl TransformConfig for DetectExceptionsConfig {
 
 pub struct DetectExceptions {
     accumulators: HashMap<Discriminant, TraceAccumulator>,
+    /// Discriminants ordered from least- (front) to most- (back) recently updated, used to pick
+    /// eviction candidates when `total_max_bytes` is exceeded.
+    access_order: VecDeque<Discriminant>,
+    /// Sum of `TraceAccumulator::buffer_size()` across every entry in `accumulators`.
+    current_bytes: usize,
     languages: Vec<ProgrammingLanguages>,
+    custom_rules: Vec<CustomRule>,
     expire_after: Duration,
     flush_period: Duration,
     multiline_flush_interval: Duration,
     max_bytes: usize,
     max_lines: usize,
+    join_separator: String,
+    annotate_combined_events: bool,
+    total_max_bytes: usize,
     group_by: Vec<String>,
+    severity_field: Option<String>,
+    min_severity: Severity,
+    stream_identity_key: Option<String>,
+    max_streams: usize,
 }
 
 impl DetectExceptions {
@@ -196,18 +369,60 @@ impl DetectExceptions {
         }
         Ok(DetectExceptions {
             accumulators: HashMap::new(),
+            access_order: VecDeque::new(),
+            current_bytes: 0,
             languages: config.languages.clone(),
+            custom_rules: config.custom_rules.clone(),
             group_by: config.group_by.clone(),
             expire_after: config.expire_after_ms,
             multiline_flush_interval: config.multiline_flush_interval_ms,
             max_bytes: config.max_bytes,
             max_lines: config.max_lines,
+            join_separator: config.join_separator.clone(),
+            annotate_combined_events: config.annotate_combined_events,
+            total_max_bytes: config.total_max_bytes,
             flush_period: config.flush_period_ms,
+            severity_field: config.severity_field.clone(),
+            min_severity: config.min_severity,
+            stream_identity_key: config.stream_identity_key.clone(),
+            max_streams: config.max_streams,
         })
     }
 
+    /// Moves `discriminant` to the back of `access_order`, marking it as most-recently-updated.
+    fn touch(&mut self, discriminant: &Discriminant) {
+        if let Some(index) = self.access_order.iter().position(|d| d == discriminant) {
+            self.access_order.remove(index);
+        }
+        self.access_order.push_back(discriminant.to_owned());
+    }
+
+    fn untrack(&mut self, discriminant: &Discriminant) {
+        if let Some(index) = self.access_order.iter().position(|d| d == discriminant) {
+            self.access_order.remove(index);
+        }
+    }
+
     fn consume_one(&mut self, output: &mut Vec<Event>, e: Event) {
         let log_event = e.into_log();
+
+        if let Some(field) = &self.severity_field {
+            let meets_threshold = log_event
+                .get(field.as_str())
+                .and_then(Severity::from_value)
+                .map_or(true, |severity| severity >= self.min_severity);
+            if !meets_threshold {
+                output.push(Event::Log(log_event));
+                return;
+            }
+        }
+
+        let byte_size = log_event
+            .get(crate::config::log_schema().message_key_target_path().unwrap())
+            .map(|v| v.to_string_lossy().len())
+            .unwrap_or(0);
+        emit!(DetectExceptionsEventsReceived { count: 1, byte_size });
+
         let discriminant = Discriminant::from_log_event(&log_event, &self.group_by);
 
         if !self.accumulators.contains_key(&discriminant) {
@@ -215,30 +430,85 @@ impl DetectExceptions {
                 discriminant.clone(),
                 TraceAccumulator::new(
                     self.languages.clone(),
+                    self.custom_rules.clone(),
                     self.multiline_flush_interval,
                     self.max_bytes,
                     self.max_lines,
+                    self.join_separator.clone(),
+                    self.annotate_combined_events,
+                    self.stream_identity_key.clone(),
+                    self.max_streams,
                 ),
             );
         }
         let accumulator = self.accumulators.get_mut(&discriminant).unwrap();
+        let bytes_before = accumulator.buffer_size();
         accumulator.push(&log_event, output);
+        let bytes_after = accumulator.buffer_size();
+        self.current_bytes = self
+            .current_bytes
+            .saturating_sub(bytes_before)
+            .saturating_add(bytes_after);
+
+        self.touch(&discriminant);
+        self.enforce_total_budget(output);
+        emit!(DetectExceptionsAccumulatorsActive {
+            count: self.accumulators.len()
+        });
+    }
+
+    /// Force-flushes the least-recently-updated groups, evicting their (now-empty) accumulators,
+    /// until `current_bytes` is back within `total_max_bytes`. A `total_max_bytes` of 0 disables
+    /// this check.
+    fn enforce_total_budget(&mut self, output: &mut Vec<Event>) {
+        if self.total_max_bytes == 0 {
+            return;
+        }
+
+        while self.current_bytes > self.total_max_bytes {
+            let Some(discriminant) = self.access_order.pop_front() else {
+                break;
+            };
+            let Some(accumulator) = self.accumulators.get_mut(&discriminant) else {
+                continue;
+            };
+
+            debug!("evicting {:?} to stay within total_max_bytes", discriminant);
+            self.current_bytes = self
+                .current_bytes
+                .saturating_sub(accumulator.buffer_size());
+            accumulator.force_flush(DetectExceptionsFlushReason::MaxBytes, output);
+            self.accumulators.remove(&discriminant);
+        }
     }
 
     fn flush_all_into(&mut self, output: &mut Vec<Event>) {
         for (k, v) in &mut self.accumulators {
-            debug!("flushing {:?}, size: {}", k, v.accumulated_messages.len());
+            debug!("flushing {:?}, empty: {}", k, v.is_empty());
+            if !v.is_empty() {
+                emit!(DetectExceptionsBufferFlushed {
+                    reason: DetectExceptionsFlushReason::StreamEnd
+                });
+            }
             v.flush(output);
         }
+        self.current_bytes = 0;
+        self.access_order.clear();
+        emit!(DetectExceptionsAccumulatorsActive { count: 0 });
     }
 
     fn flush_stale_into(&mut self, output: &mut Vec<Event>) {
         let now = Utc::now();
         let mut for_removal: Vec<Discriminant> = vec![];
         for (k, v) in &mut self.accumulators {
+            let bytes_before = v.buffer_size();
             v.flush_stale_into(now, output);
-            if v.accumulated_messages.len() == 0 {
-                if now.timestamp_millis() - v.buffer_start_time.timestamp_millis()
+            self.current_bytes = self
+                .current_bytes
+                .saturating_sub(bytes_before)
+                .saturating_add(v.buffer_size());
+            if v.is_empty() {
+                if now.timestamp_millis() - v.last_activity.timestamp_millis()
                     > self.expire_after.as_millis().try_into().unwrap()
                 {
                     for_removal.push(k.to_owned());
@@ -247,8 +517,15 @@ impl DetectExceptions {
         }
         for d in for_removal {
             debug!("removing {:?}", d);
+            emit!(DetectExceptionsBufferFlushed {
+                reason: DetectExceptionsFlushReason::Expired
+            });
             self.accumulators.remove(&d);
+            self.untrack(&d);
         }
+        emit!(DetectExceptionsAccumulatorsActive {
+            count: self.accumulators.len()
+        });
     }
 }
 
@@ -373,4 +650,31 @@ Jul 09, 2015 3:23:29 PM com.google.devtools.search.cloud.feeder.MakeLog: Runtime
         assert_eq!(output_2["message"], java_simple_log.trim().into());
         assert_eq!(output_2["counter"], Value::from(6));
     }
+
+    #[test]
+    fn total_max_bytes_evicts_least_recently_updated_group() {
+        let config = DetectExceptionsConfig {
+            languages: vec![ProgrammingLanguages::All],
+            group_by: vec!["group".to_string()],
+            total_max_bytes: 10,
+            ..Default::default()
+        };
+        let mut detect_exceptions = DetectExceptions::new(&config).unwrap();
+
+        let mut output = Vec::new();
+        // Each message opens a Go panic trace, so it is buffered rather than passed straight
+        // through; "panic: a" is 8 bytes, so one group fits the budget and two do not.
+        for (group, message) in [("a", "panic: a"), ("b", "panic: b")] {
+            let mut le = LogEvent::default();
+            le.insert("group", group);
+            le.insert("message", message);
+            detect_exceptions.consume_one(&mut output, Event::Log(le));
+        }
+
+        // Pushing "b" pushes the combined buffer over `total_max_bytes`, which should evict "a"
+        // (the least-recently-updated group) by force-flushing it rather than dropping it.
+        assert_eq!(output.len(), 1);
+        assert_eq!(output[0].clone().into_log()["group"], "a".into());
+        assert_eq!(detect_exceptions.accumulators.len(), 1);
+    }
 }
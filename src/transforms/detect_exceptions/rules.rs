@@ -1,6 +1,6 @@
 use std::collections::HashMap;
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Copy)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ExceptionState {
     /// StartState for all languages
     StartState,
@@ -42,6 +42,25 @@ pub enum ExceptionState {
     DartMethodErr1,
     DartMethodErr2,
     DartMethodErr3,
+
+    /// A state declared by a user-defined custom grammar, identified by the name it was given in
+    /// that grammar's rules. Scoped per grammar: a `Custom("stack")` from one set of custom rules
+    /// is unrelated to a `Custom("stack")` from another, since they're only ever populated
+    /// together from the same `custom_rules` config list.
+    Custom(String),
+}
+
+impl ExceptionState {
+    /// Resolves a custom rule's `from_states`/`to_state` name into a concrete `ExceptionState`.
+    /// The literal name `StartState` maps onto the shared idle/reset state so custom grammars
+    /// compose with the built-in ones (and with each other); any other name becomes a `Custom`
+    /// state private to that grammar.
+    pub fn from_custom_name(name: &str) -> ExceptionState {
+        match name {
+            "StartState" => ExceptionState::StartState,
+            _ => ExceptionState::Custom(name.to_owned()),
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
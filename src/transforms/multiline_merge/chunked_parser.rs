@@ -0,0 +1,101 @@
+/// Buffers raw byte chunks into complete newline-terminated lines, tolerating chunk boundaries
+/// that split a line's bytes across reads (e.g. a socket delivering half of a `File "..."` line
+/// in one read and the rest in the next). `process` only ever returns complete lines; an
+/// incomplete trailing line is retained until a later call completes it, or `finish` flushes it
+/// as-is.
+#[derive(Debug, Default)]
+pub struct Parser {
+    buffer: Vec<u8>,
+}
+
+impl Parser {
+    pub fn new() -> Parser {
+        Parser { buffer: Vec::new() }
+    }
+
+    /// Appends `chunk` to the internal buffer and returns every complete line found, in order.
+    /// Lines are split on `\n`; a trailing `\r` is trimmed.
+    pub fn process(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(chunk);
+
+        let mut lines = Vec::new();
+        while let Some(pos) = self.buffer.iter().position(|&b| b == b'\n') {
+            let mut line: Vec<u8> = self.buffer.drain(..=pos).collect();
+            line.pop(); // trailing '\n'
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            lines.push(String::from_utf8_lossy(&line).into_owned());
+        }
+        lines
+    }
+
+    /// Flushes whatever incomplete line is still buffered, if any. Call once the underlying
+    /// stream has ended so a final line with no trailing newline isn't lost.
+    pub fn finish(&mut self) -> Option<String> {
+        if self.buffer.is_empty() {
+            return None;
+        }
+        let mut line = std::mem::take(&mut self.buffer);
+        if line.last() == Some(&b'\r') {
+            line.pop();
+        }
+        Some(String::from_utf8_lossy(&line).into_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn process_whole(input: &[u8]) -> Vec<String> {
+        let mut parser = Parser::new();
+        let mut lines = parser.process(input);
+        if let Some(rest) = parser.finish() {
+            lines.push(rest);
+        }
+        lines
+    }
+
+    fn process_split_at(input: &[u8], offset: usize) -> Vec<String> {
+        let mut parser = Parser::new();
+        let mut lines = parser.process(&input[..offset]);
+        lines.extend(parser.process(&input[offset..]));
+        if let Some(rest) = parser.finish() {
+            lines.push(rest);
+        }
+        lines
+    }
+
+    const FIXTURES: &[&str] = &[
+        "Traceback (most recent call last):\n  File \"/app/example.py\", line 5, in <module>\n    raise Exception('spam', 'eggs')\nException: ('spam', 'eggs')\n",
+        "single line with no trailing newline",
+        "",
+        "\n\n\n",
+        "line one\nline two\nline three",
+    ];
+
+    #[test]
+    fn splitting_at_every_byte_offset_yields_the_same_lines() {
+        for fixture in FIXTURES {
+            let input = fixture.as_bytes();
+            let expected = process_whole(input);
+            for offset in 0..=input.len() {
+                assert_eq!(
+                    expected,
+                    process_split_at(input, offset),
+                    "fixture {:?} split at offset {}",
+                    fixture,
+                    offset
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn finish_returns_none_when_buffer_ends_on_a_newline() {
+        let mut parser = Parser::new();
+        assert_eq!(parser.process(b"a line\n"), vec!["a line".to_string()]);
+        assert_eq!(parser.finish(), None);
+    }
+}
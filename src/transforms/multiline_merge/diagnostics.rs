@@ -0,0 +1,259 @@
+use serde::Deserialize;
+
+/// Severity of a structured compiler/linter diagnostic, ordered from least to most severe.
+/// Mirrors rustc's own internal ordering, so diagnostics from different tools can be merged and
+/// filtered by a single severity threshold regardless of source.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    /// A note attached to a diagnostic that failed to render (e.g. a bug in the tool itself).
+    FailureNote,
+    /// An informational note.
+    Note,
+    /// A suggested fix or further reading.
+    Help,
+    /// A non-fatal warning.
+    Warn,
+    /// A hard error.
+    Error,
+    /// An internal compiler error ("ICE") — a bug in the tool, not the input.
+    Ice,
+}
+
+impl Level {
+    fn from_wire(s: &str) -> Level {
+        match s {
+            "error: internal compiler error" => Level::Ice,
+            "error" => Level::Error,
+            "warning" => Level::Warn,
+            "help" => Level::Help,
+            "failure-note" => Level::FailureNote,
+            _ => Level::Note,
+        }
+    }
+}
+
+/// Sentinel used in place of a real line/column number when a `Span` couldn't be resolved to a
+/// real source location (lines and columns are otherwise 1-based, per rustc's own convention).
+pub const INVALID: u32 = 0;
+
+/// A resolved source location: a file plus a 1-based start/end line and column. Use [`INVALID`]
+/// in place of any of the four position fields to represent "unknown".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub file: String,
+    pub line_start: u32,
+    pub column_start: u32,
+    pub line_end: u32,
+    pub column_end: u32,
+}
+
+impl Span {
+    #[cfg(test)]
+    fn invalid() -> Span {
+        Span {
+            file: String::new(),
+            line_start: INVALID,
+            column_start: INVALID,
+            line_end: INVALID,
+            column_end: INVALID,
+        }
+    }
+
+    fn from_raw(raw: &RawSpan) -> Span {
+        Span {
+            file: raw.file_name.clone(),
+            line_start: raw.line_start,
+            column_start: raw.column_start,
+            line_end: raw.line_end,
+            column_end: raw.column_end,
+        }
+    }
+}
+
+/// A single parsed diagnostic message: a severity, the human-readable text, the primary source
+/// span (if any), the tool's own pretty-printed rendering (if any), and any nested notes/helps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub level: Level,
+    pub message: String,
+    pub span: Option<Span>,
+    pub rendered: Option<String>,
+    pub children: Vec<Diagnostic>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawSpan {
+    file_name: String,
+    line_start: u32,
+    line_end: u32,
+    column_start: u32,
+    column_end: u32,
+    #[serde(default)]
+    is_primary: bool,
+    #[serde(default)]
+    expansion: Option<Box<RawExpansion>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawExpansion {
+    span: RawSpan,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawDiagnostic {
+    message: String,
+    level: String,
+    #[serde(default)]
+    spans: Vec<RawSpan>,
+    #[serde(default)]
+    children: Vec<RawDiagnostic>,
+    #[serde(default)]
+    rendered: Option<String>,
+}
+
+/// Resolves a span up through any `expansion` chain (a macro-generated span points at the site it
+/// expanded from) to the outermost, ultimately-real source location.
+fn resolve_span(mut raw: &RawSpan) -> Span {
+    while let Some(expansion) = &raw.expansion {
+        raw = &expansion.span;
+    }
+    Span::from_raw(raw)
+}
+
+fn primary_span(raw: &RawDiagnostic) -> Option<Span> {
+    raw.spans
+        .iter()
+        .find(|span| span.is_primary)
+        .map(resolve_span)
+}
+
+fn convert(raw: RawDiagnostic) -> Diagnostic {
+    Diagnostic {
+        level: Level::from_wire(&raw.level),
+        span: primary_span(&raw),
+        message: raw.message,
+        rendered: raw.rendered,
+        children: raw.children.into_iter().map(convert).collect(),
+    }
+}
+
+/// Parses a single line of structured compiler/linter diagnostic JSON (e.g. one line of rustc's
+/// `--error-format=json` output) into a [`Diagnostic`].
+pub fn parse_diagnostic(line: &str) -> serde_json::Result<Diagnostic> {
+    let raw: RawDiagnostic = serde_json::from_str(line)?;
+    Ok(convert(raw))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn orders_levels_by_severity() {
+        assert!(Level::Note < Level::Help);
+        assert!(Level::Help < Level::Warn);
+        assert!(Level::Warn < Level::Error);
+        assert!(Level::Error < Level::Ice);
+        assert!(Level::FailureNote < Level::Note);
+    }
+
+    #[test]
+    fn parses_simple_error_with_primary_span() {
+        let line = r#"{
+            "message": "mismatched types",
+            "level": "error",
+            "spans": [
+                {
+                    "file_name": "src/main.rs",
+                    "line_start": 4,
+                    "line_end": 4,
+                    "column_start": 9,
+                    "column_end": 11,
+                    "is_primary": true
+                }
+            ],
+            "children": [],
+            "rendered": "error: mismatched types\n --> src/main.rs:4:9"
+        }"#;
+
+        let diagnostic = parse_diagnostic(line).unwrap();
+        assert_eq!(diagnostic.level, Level::Error);
+        assert_eq!(diagnostic.message, "mismatched types");
+        assert_eq!(
+            diagnostic.span,
+            Some(Span {
+                file: "src/main.rs".to_string(),
+                line_start: 4,
+                line_end: 4,
+                column_start: 9,
+                column_end: 11,
+            })
+        );
+        assert!(diagnostic.children.is_empty());
+    }
+
+    #[test]
+    fn recurses_into_child_notes() {
+        let line = r#"{
+            "message": "unused variable: `x`",
+            "level": "warning",
+            "spans": [],
+            "children": [
+                {
+                    "message": "consider prefixing with an underscore",
+                    "level": "help",
+                    "spans": [],
+                    "children": []
+                }
+            ]
+        }"#;
+
+        let diagnostic = parse_diagnostic(line).unwrap();
+        assert_eq!(diagnostic.level, Level::Warn);
+        assert_eq!(diagnostic.children.len(), 1);
+        assert_eq!(diagnostic.children[0].level, Level::Help);
+        assert_eq!(diagnostic.span, None);
+    }
+
+    #[test]
+    fn resolves_through_macro_expansion_to_outermost_span() {
+        let line = r#"{
+            "message": "this is expanded from a macro",
+            "level": "error",
+            "spans": [
+                {
+                    "file_name": "src/macro_def.rs",
+                    "line_start": 2,
+                    "line_end": 2,
+                    "column_start": 5,
+                    "column_end": 8,
+                    "is_primary": true,
+                    "expansion": {
+                        "span": {
+                            "file_name": "src/main.rs",
+                            "line_start": 10,
+                            "line_end": 10,
+                            "column_start": 1,
+                            "column_end": 20,
+                            "is_primary": false
+                        }
+                    }
+                }
+            ],
+            "children": []
+        }"#;
+
+        let diagnostic = parse_diagnostic(line).unwrap();
+        assert_eq!(diagnostic.span.unwrap().file, "src/main.rs");
+    }
+
+    #[test]
+    fn missing_span_falls_back_to_none() {
+        let diagnostic = parse_diagnostic(
+            r#"{"message": "no spans here", "level": "note", "spans": [], "children": []}"#,
+        )
+        .unwrap();
+        assert_eq!(diagnostic.span, None);
+        assert_eq!(Span::invalid().line_start, INVALID);
+    }
+}
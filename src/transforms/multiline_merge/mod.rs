@@ -0,0 +1,449 @@
+mod chunked_parser;
+mod diagnostics;
+mod rules;
+mod splitter;
+
+pub use chunked_parser::Parser;
+pub use diagnostics::{parse_diagnostic, Diagnostic, Level, Span};
+pub use splitter::{split, split_exact, Delimiter, SplitterConfig, UnexpectedFieldCount};
+
+use chrono::{DateTime, Utc};
+use regex::{Regex, RegexSet};
+use serde_with::serde_as;
+
+use crate::{
+    config::{
+        log_schema, DataType, Input, OutputId, TransformConfig, TransformContext,
+        TransformDescription, TransformOutput,
+    },
+    event::{Event, LogEvent},
+    internal_events::multiline_merge::{
+        MultilineMergeBufferFlushed, MultilineMergeEventsCombined, MultilineMergeEventsReceived,
+        MultilineMergeFlushReason,
+    },
+    schema,
+    transforms::{TaskTransform, Transform},
+};
+use async_stream::stream;
+use futures::{stream, Stream, StreamExt};
+use std::{pin::Pin, time::Duration};
+use vector_config::configurable_component;
+use vector_core::config::clone_input_definitions;
+use vector_core::config::LogNamespace;
+
+/// Built-in continuation rule sets for the `multiline_merge` transform.
+#[configurable_component]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MultilineLanguage {
+    /// Python tracebacks.
+    Python,
+
+    /// Java/JVM stack traces.
+    Java,
+
+    /// Go panics and goroutine dumps.
+    Go,
+
+    /// Ruby backtraces.
+    Ruby,
+
+    /// Node.js stack traces.
+    Node,
+
+    /// All built-in languages.
+    All,
+}
+
+/// Configuration for the `multiline_merge` transform.
+#[serde_as]
+#[configurable_component(transform("multiline_merge"))]
+#[derive(Debug, Clone)]
+#[serde(deny_unknown_fields, default)]
+pub struct MultilineMergeConfig {
+    /// Built-in continuation rule sets to apply.
+    ///
+    /// Supported languages are
+    ///   - Java
+    ///   - Python
+    ///   - Go
+    ///   - Ruby
+    ///   - Node
+    ///   - All (includes all above)
+    #[serde(default = "default_languages")]
+    pub languages: Vec<MultilineLanguage>,
+
+    /// Regex identifying a "start" line, i.e. the first line of a new event.
+    ///
+    /// When unset (the default), a line starts a new event unless it's indented (begins with
+    /// whitespace) or matches one of `languages`' continuation patterns; otherwise it's treated
+    /// as a continuation of the line(s) already buffered.
+    #[serde(default)]
+    pub start_pattern: Option<String>,
+
+    /// The maximum period of time to wait after the last line is received, in milliseconds,
+    /// before the buffered event is flushed even though no new start line has arrived.
+    #[serde(default = "default_timeout_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    pub timeout_ms: Duration,
+
+    /// The interval to check for and flush a timed-out buffer, in milliseconds.
+    #[serde(default = "default_flush_period_ms")]
+    #[serde_as(as = "serde_with::DurationMilliSeconds<u64>")]
+    pub flush_period_ms: Duration,
+
+    /// Maximum number of lines to buffer before force-flushing (0 means no limit). Default: 1000.
+    #[serde(default = "default_max_lines")]
+    pub max_lines: usize,
+
+    /// Maximum number of bytes to buffer before force-flushing (0 means no limit). Default: 0.
+    #[serde(default)]
+    pub max_bytes: usize,
+}
+
+impl Default for MultilineMergeConfig {
+    fn default() -> Self {
+        Self {
+            languages: default_languages(),
+            start_pattern: None,
+            timeout_ms: default_timeout_ms(),
+            flush_period_ms: default_flush_period_ms(),
+            max_lines: default_max_lines(),
+            max_bytes: 0,
+        }
+    }
+}
+
+fn default_languages() -> Vec<MultilineLanguage> {
+    vec![MultilineLanguage::All]
+}
+
+const fn default_timeout_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_flush_period_ms() -> Duration {
+    Duration::from_millis(1000)
+}
+
+const fn default_max_lines() -> usize {
+    1000
+}
+
+impl_generate_config_from_default!(MultilineMergeConfig);
+inventory::submit! {
+    TransformDescription::new::<MultilineMergeConfig>("multiline_merge", "multiline_merge", "multiline_merge", "multiline_merge")
+}
+
+#[async_trait::async_trait]
+#[typetag::serde(name = "multiline_merge")]
+impl TransformConfig for MultilineMergeConfig {
+    async fn build(&self, _context: &TransformContext) -> crate::Result<Transform> {
+        MultilineMerge::new(self).map(Transform::event_task)
+    }
+
+    fn input(&self) -> Input {
+        Input::log()
+    }
+
+    fn outputs(
+        &self,
+        _: enrichment::TableRegistry,
+        input_definitions: &[(OutputId, schema::Definition)],
+        _: LogNamespace,
+    ) -> Vec<TransformOutput> {
+        vec![TransformOutput::new(
+            DataType::Log,
+            clone_input_definitions(input_definitions),
+        )]
+    }
+}
+
+/// Classifies a line as the start of a new event or a continuation of the one being buffered.
+/// With no `start_pattern` configured, a line starts a new event unless it's indented or matches
+/// one of the built-in continuation patterns for `languages`.
+struct LineClassifier {
+    start_regex: Option<Regex>,
+    continuation_set: Option<RegexSet>,
+}
+
+impl LineClassifier {
+    fn new(start_pattern: &Option<String>, languages: &[MultilineLanguage]) -> LineClassifier {
+        let continuation_patterns: Vec<&str> = languages
+            .iter()
+            .flat_map(|lang| rules::continuation_patterns(*lang))
+            .collect();
+        LineClassifier {
+            start_regex: start_pattern
+                .as_ref()
+                .map(|pattern| Regex::new(pattern).unwrap()),
+            continuation_set: if continuation_patterns.is_empty() {
+                None
+            } else {
+                Some(RegexSet::new(continuation_patterns).unwrap())
+            },
+        }
+    }
+
+    fn is_start_line(&self, line: &str) -> bool {
+        if let Some(start_regex) = &self.start_regex {
+            return start_regex.is_match(line);
+        }
+        if line.starts_with(char::is_whitespace) {
+            return false;
+        }
+        !matches!(&self.continuation_set, Some(set) if set.is_match(line))
+    }
+}
+
+pub struct MultilineMerge {
+    classifier: LineClassifier,
+    timeout: Duration,
+    flush_period: Duration,
+    max_lines: usize,
+    max_bytes: usize,
+    first_event: Option<LogEvent>,
+    buffered_lines: Vec<String>,
+    buffer_size: usize,
+    last_activity: DateTime<Utc>,
+}
+
+impl MultilineMerge {
+    pub fn new(config: &MultilineMergeConfig) -> crate::Result<Self> {
+        if config.languages.is_empty() {
+            return Err("languages cannot be empty".into());
+        }
+        Ok(MultilineMerge {
+            classifier: LineClassifier::new(&config.start_pattern, &config.languages),
+            timeout: config.timeout_ms,
+            flush_period: config.flush_period_ms,
+            max_lines: config.max_lines,
+            max_bytes: config.max_bytes,
+            first_event: None,
+            buffered_lines: vec![],
+            buffer_size: 0,
+            last_activity: Utc::now(),
+        })
+    }
+
+    fn push(&mut self, le: LogEvent, output: &mut Vec<Event>) {
+        let byte_size = le
+            .get(log_schema().message_key_target_path().unwrap())
+            .map(|v| v.to_string_lossy().len())
+            .unwrap_or(0);
+        emit!(MultilineMergeEventsReceived {
+            count: 1,
+            byte_size
+        });
+
+        self.last_activity = Utc::now();
+
+        let message = le.get(log_schema().message_key_target_path().unwrap());
+        let Some(line) = message.map(|v| v.to_string_lossy().into_owned()) else {
+            self.flush(output);
+            output.push(Event::Log(le));
+            return;
+        };
+
+        if !self.buffered_lines.is_empty() && !self.classifier.is_start_line(&line) {
+            self.add(le, line);
+            if self.max_lines > 0 && self.buffered_lines.len() >= self.max_lines {
+                self.force_flush(MultilineMergeFlushReason::MaxLines, output);
+            } else if self.max_bytes > 0 && self.buffer_size > self.max_bytes {
+                self.force_flush(MultilineMergeFlushReason::MaxBytes, output);
+            }
+            return;
+        }
+
+        if !self.buffered_lines.is_empty() {
+            self.force_flush(MultilineMergeFlushReason::StartLine, output);
+        }
+        self.add(le, line);
+    }
+
+    fn add(&mut self, le: LogEvent, line: String) {
+        if self.buffered_lines.is_empty() {
+            self.first_event = Some(le);
+        }
+        self.buffer_size += line.len();
+        self.buffered_lines.push(line);
+    }
+
+    /// Flushes whatever is buffered into `first_event`, recording the number of physical lines
+    /// joined in `multiline.line_count`. A no-op if nothing is buffered.
+    fn flush(&mut self, output: &mut Vec<Event>) {
+        let Some(mut first_event) = self.first_event.take() else {
+            return;
+        };
+        let line_count = self.buffered_lines.len();
+        if line_count > 1 {
+            let joined = self.buffered_lines.join("\n");
+            emit!(MultilineMergeEventsCombined {
+                lines: line_count,
+                byte_size: joined.len(),
+            });
+            first_event.insert(log_schema().message_key_target_path().unwrap(), joined);
+        }
+        first_event.insert("multiline.line_count", line_count as i64);
+        output.push(Event::Log(first_event));
+        self.buffered_lines.clear();
+        self.buffer_size = 0;
+    }
+
+    fn force_flush(&mut self, reason: MultilineMergeFlushReason, output: &mut Vec<Event>) {
+        let had_buffered = !self.buffered_lines.is_empty();
+        self.flush(output);
+        if had_buffered {
+            emit!(MultilineMergeBufferFlushed { reason });
+        }
+    }
+
+    fn flush_stale_into(&mut self, now: DateTime<Utc>, output: &mut Vec<Event>) {
+        if !self.buffered_lines.is_empty()
+            && now.timestamp_millis() - self.last_activity.timestamp_millis()
+                > self.timeout.as_millis().try_into().unwrap()
+        {
+            self.force_flush(MultilineMergeFlushReason::Timeout, output);
+        }
+    }
+
+    fn flush_all_into(&mut self, output: &mut Vec<Event>) {
+        if !self.buffered_lines.is_empty() {
+            emit!(MultilineMergeBufferFlushed {
+                reason: MultilineMergeFlushReason::StreamEnd
+            });
+        }
+        self.flush(output);
+    }
+}
+
+impl TaskTransform<Event> for MultilineMerge {
+    fn transform(
+        self: Box<Self>,
+        mut input_rx: Pin<Box<dyn Stream<Item = Event> + Send>>,
+    ) -> Pin<Box<dyn Stream<Item = Event> + Send>>
+    where
+        Self: 'static,
+    {
+        let mut me = self;
+
+        let poll_period = me.flush_period;
+
+        let mut flush_stream = tokio::time::interval(poll_period);
+
+        Box::pin(
+            stream! {
+              loop {
+                let mut output = Vec::new();
+                let done = tokio::select! {
+                    _ = flush_stream.tick() => {
+                      me.flush_stale_into(Utc::now(), &mut output);
+                      false
+                    }
+                    maybe_event = input_rx.next() => {
+                      match maybe_event {
+                        None => {
+                          me.flush_all_into(&mut output);
+                          true
+                        }
+                        Some(event) => {
+                          me.push(event.into_log(), &mut output);
+                          false
+                        }
+                      }
+                    }
+                };
+                yield stream::iter(output.into_iter());
+                if done { break }
+              }
+            }
+            .flatten(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+
+    use super::*;
+    use crate::{config::TransformConfig, event::Value};
+
+    #[test]
+    fn generate_config() {
+        crate::test_util::test_generate_config::<MultilineMergeConfig>();
+    }
+
+    #[test]
+    fn test_generate_config() {
+        toml::from_str::<MultilineMergeConfig>(
+            r#"
+languages = ["All"]
+timeout_ms = 2000
+max_lines = 500
+"#,
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_python_traceback_merged() {
+        let multiline_merge = toml::from_str::<MultilineMergeConfig>(
+            r#"
+languages = ["Python"]
+"#,
+        )
+        .unwrap()
+        .build(&TransformContext::default())
+        .await
+        .unwrap();
+
+        let multiline_merge = multiline_merge.into_task();
+
+        let traceback = r#"Traceback (most recent call last):
+  File "/app/example.py", line 5, in <module>
+    raise Exception('spam', 'eggs')
+Exception: ('spam', 'eggs')"#;
+        let next_log_line = "INFO starting new request";
+
+        let lines: Vec<&str> = traceback.split('\n').chain([next_log_line]).collect();
+        let input_events: Vec<Event> = lines
+            .into_iter()
+            .map(|line| Event::Log(LogEvent::from(line)))
+            .collect();
+
+        let in_stream = Box::pin(stream::iter(input_events));
+        let mut out_stream = multiline_merge.transform_events(in_stream);
+
+        let output_1 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_1["message"], traceback.into());
+        assert_eq!(output_1["multiline.line_count"], Value::from(4));
+
+        let output_2 = out_stream.next().await.unwrap().into_log();
+        assert_eq!(output_2["message"], next_log_line.into());
+        assert_eq!(output_2["multiline.line_count"], Value::from(1));
+    }
+
+    #[test]
+    fn max_lines_force_flushes_buffer() {
+        let config = MultilineMergeConfig {
+            languages: vec![MultilineLanguage::Python],
+            max_lines: 2,
+            ..Default::default()
+        };
+        let mut multiline_merge = MultilineMerge::new(&config).unwrap();
+
+        let mut output = Vec::new();
+        for line in [
+            "Traceback (most recent call last):",
+            "  File \"/app/example.py\", line 5, in <module>",
+            "    raise Exception('spam', 'eggs')",
+        ] {
+            multiline_merge.push(LogEvent::from(line), &mut output);
+        }
+
+        assert_eq!(output.len(), 1);
+        assert_eq!(
+            output[0].clone().into_log()["multiline.line_count"],
+            Value::from(2)
+        );
+    }
+}
@@ -0,0 +1,22 @@
+use super::MultilineLanguage;
+
+/// Built-in continuation-line patterns for `lang`. A line matching any of these, while a buffer is
+/// already open, is treated as a continuation of the current event rather than the start of a new
+/// one.
+pub fn continuation_patterns(lang: MultilineLanguage) -> Vec<&'static str> {
+    use MultilineLanguage::*;
+    match lang {
+        Python => vec![r#"^\s+File ""#, r"^\s+", r"^\w*(Error|Exception):"],
+        Java => vec![r"^\s+at ", r"^Caused by:", r"^\t\.\.\. \d+ more"],
+        Go => vec![r"^\tgoroutine", r"\(0x[0-9a-f]+(?:,\s*0x[0-9a-f]+)*\)$"],
+        Ruby => vec![r"^\s+from "],
+        Node => vec![r"^\s+at "],
+        All => {
+            let mut patterns = vec![];
+            for lang in [Python, Java, Go, Ruby, Node] {
+                patterns.extend(continuation_patterns(lang));
+            }
+            patterns
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use std::fmt;
+
+/// The separator used to frame records, in place of a hardcoded `\n`. Any string may be used as a
+/// delimiter; a single-character string (the ASCII record separator `\u{1e}`, `\0`, etc.) lets
+/// records framed on a control character contain literal newlines of their own — e.g. a JSON blob
+/// or an already-merged multi-line traceback.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Delimiter(String);
+
+impl Delimiter {
+    pub fn new(value: impl Into<String>) -> Delimiter {
+        Delimiter(value.into())
+    }
+
+    /// The ASCII record separator (`0x1e`), a common choice for framing records that may
+    /// themselves contain newlines.
+    pub fn ascii_rs() -> Delimiter {
+        Delimiter('\u{1e}'.to_string())
+    }
+
+    /// A NUL byte, another common non-newline record separator.
+    pub fn nul() -> Delimiter {
+        Delimiter('\0'.to_string())
+    }
+
+    fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Delimiter {
+    fn default() -> Self {
+        Delimiter("\n".to_string())
+    }
+}
+
+/// Controls how [`split`] frames and cleans up records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SplitterConfig {
+    /// The separator between records. Defaults to `"\n"`.
+    pub delimiter: Delimiter,
+    /// When `true`, `"\r\n"` is treated the same as `"\n"` before splitting, so CRLF- and
+    /// LF-terminated input produce identical records regardless of `delimiter`.
+    pub normalize_crlf: bool,
+    /// When `true`, records whose trimmed value is empty are dropped, matching the common
+    /// "ignore blank lines" behavior. Evaluated against the trimmed value even when `trim` is
+    /// `false`, so blank-but-indented records are still dropped.
+    pub drop_empty: bool,
+    /// When `true` (the default), leading and trailing whitespace is trimmed from each record.
+    /// Set to `false` to preserve leading indentation, e.g. for stack frames where it's
+    /// meaningful.
+    pub trim: bool,
+}
+
+impl Default for SplitterConfig {
+    fn default() -> Self {
+        SplitterConfig {
+            delimiter: Delimiter::default(),
+            normalize_crlf: false,
+            drop_empty: false,
+            trim: true,
+        }
+    }
+}
+
+/// Splits `input` into records per `config`.
+pub fn split(input: &str, config: &SplitterConfig) -> Vec<String> {
+    let normalized;
+    let input = if config.normalize_crlf {
+        normalized = input.replace("\r\n", "\n");
+        normalized.as_str()
+    } else {
+        input
+    };
+
+    input
+        .split(config.delimiter.as_str())
+        .map(|segment| {
+            if config.trim {
+                segment.trim().to_string()
+            } else {
+                segment.to_string()
+            }
+        })
+        .filter(|segment| !config.drop_empty || !segment.trim().is_empty())
+        .collect()
+}
+
+/// Returned by [`split_exact`] when `input` doesn't split into exactly the expected number of
+/// records.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnexpectedFieldCount {
+    pub expected: usize,
+    pub actual: usize,
+}
+
+impl fmt::Display for UnexpectedFieldCount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected exactly {} field(s), got {}",
+            self.expected, self.actual
+        )
+    }
+}
+
+impl std::error::Error for UnexpectedFieldCount {}
+
+/// Splits `input` per `config`, requiring the result to contain exactly `expected` records.
+pub fn split_exact(
+    input: &str,
+    config: &SplitterConfig,
+    expected: usize,
+) -> Result<Vec<String>, UnexpectedFieldCount> {
+    let records = split(input, config);
+    if records.len() != expected {
+        return Err(UnexpectedFieldCount {
+            expected,
+            actual: records.len(),
+        });
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_on_newline_by_default_and_trims() {
+        let config = SplitterConfig::default();
+        assert_eq!(
+            split(" line one \n line two \n", &config),
+            vec!["line one", "line two", ""]
+        );
+    }
+
+    #[test]
+    fn normalizes_crlf_before_splitting() {
+        let config = SplitterConfig {
+            normalize_crlf: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            split("line one\r\nline two\r\n", &config),
+            vec!["line one", "line two", ""]
+        );
+    }
+
+    #[test]
+    fn drop_empty_filters_blank_segments() {
+        let config = SplitterConfig {
+            drop_empty: true,
+            ..Default::default()
+        };
+        assert_eq!(
+            split("line one\n\n   \nline two\n", &config),
+            vec!["line one", "line two"]
+        );
+    }
+
+    #[test]
+    fn trim_false_preserves_leading_indentation() {
+        let config = SplitterConfig {
+            trim: false,
+            ..Default::default()
+        };
+        assert_eq!(
+            split("line one\n  at com.example.Foo.bar()\n", &config),
+            vec!["line one", "  at com.example.Foo.bar()", ""]
+        );
+    }
+
+    #[test]
+    fn splits_on_a_non_newline_delimiter_keeping_embedded_newlines() {
+        let config = SplitterConfig {
+            delimiter: Delimiter::ascii_rs(),
+            ..Default::default()
+        };
+        let input = "{\n  \"a\": 1\n}\u{1e}{\n  \"b\": 2\n}";
+        assert_eq!(
+            split(input, &config),
+            vec!["{\n  \"a\": 1\n}", "{\n  \"b\": 2\n}"]
+        );
+    }
+
+    #[test]
+    fn split_exact_errors_when_field_count_mismatches() {
+        let config = SplitterConfig::default();
+        let err = split_exact("a\nb\nc", &config, 2).unwrap_err();
+        assert_eq!(
+            err,
+            UnexpectedFieldCount {
+                expected: 2,
+                actual: 3
+            }
+        );
+    }
+
+    #[test]
+    fn split_exact_succeeds_when_field_count_matches() {
+        let config = SplitterConfig::default();
+        assert_eq!(
+            split_exact("a\nb", &config, 2).unwrap(),
+            vec!["a".to_string(), "b".to_string()]
+        );
+    }
+}
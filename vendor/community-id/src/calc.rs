@@ -56,6 +56,23 @@ pub fn calculate_community_id(
     }
 }
 
+/// Build the Community ID string for a flow, with base64 encoding always enabled.
+///
+/// This is a thin, argument-reordered wrapper around [`calculate_community_id`] that forwards the
+/// caller's `seed` unchanged and always passes `disable_base64 = false`, for callers who don't
+/// need to turn off base64 encoding, which covers the common case of matching another sensor's
+/// default configuration.
+pub fn community_id(
+    src_ip: IpAddr,
+    dst_ip: IpAddr,
+    ip_proto: u8,
+    src_port: Option<u16>,
+    dst_port: Option<u16>,
+    seed: u16,
+) -> Result<String> {
+    calculate_community_id(seed, src_ip, dst_ip, src_port, dst_port, ip_proto, false)
+}
+
 #[cfg(test)]
 mod tests {
     use std::net::Ipv4Addr;
@@ -76,6 +93,19 @@ mod tests {
         assert_eq!("1:wCb3OG7yAFWelaUydu0D+125CLM=", id.unwrap());
     }
 
+    #[test]
+    fn test_community_id_wrapper() {
+        let id = community_id(
+            Ipv4Addr::new(1, 2, 3, 4).into(),
+            Ipv4Addr::new(5, 6, 7, 8).into(),
+            6,
+            Some(1122),
+            Some(3344),
+            0,
+        );
+        assert_eq!("1:wCb3OG7yAFWelaUydu0D+125CLM=", id.unwrap());
+    }
+
     #[test]
     fn test_tcp_without_ports() {
         let id = calculate_community_id(
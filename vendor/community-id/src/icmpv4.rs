@@ -46,3 +46,44 @@ pub(crate) fn get_port_equivalents(mtype: u16, mcode: u16) -> (u16, u16, bool) {
         Err(_) => return (mtype, mcode, true),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::get_port_equivalents;
+
+    #[test]
+    fn echo_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(8, 0), (8, 0, false));
+        assert_eq!(get_port_equivalents(0, 0), (0, 8, false));
+    }
+
+    #[test]
+    fn timestamp_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(13, 0), (13, 14, false));
+        assert_eq!(get_port_equivalents(14, 0), (14, 13, false));
+    }
+
+    #[test]
+    fn info_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(15, 0), (15, 16, false));
+        assert_eq!(get_port_equivalents(16, 0), (16, 15, false));
+    }
+
+    #[test]
+    fn address_mask_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(17, 0), (17, 18, false));
+        assert_eq!(get_port_equivalents(18, 0), (18, 17, false));
+    }
+
+    #[test]
+    fn router_solicitation_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(10, 0), (10, 9, false));
+        assert_eq!(get_port_equivalents(9, 0), (9, 10, false));
+    }
+
+    #[test]
+    fn unmapped_type_is_one_way() {
+        assert_eq!(get_port_equivalents(3, 1), (3, 1, true));
+        assert_eq!(get_port_equivalents(11, 0), (11, 0, true));
+    }
+}
@@ -50,3 +50,51 @@ pub(crate) fn get_port_equivalents(mtype: u16, mcode: u16) -> (u16, u16, bool) {
         Err(_) => return (mtype, mcode, true),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::get_port_equivalents;
+
+    #[test]
+    fn echo_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(128, 0), (128, 129, false));
+        assert_eq!(get_port_equivalents(129, 0), (129, 128, false));
+    }
+
+    #[test]
+    fn mld_listener_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(130, 0), (130, 131, false));
+        assert_eq!(get_port_equivalents(131, 0), (131, 130, false));
+    }
+
+    #[test]
+    fn router_solicitation_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(133, 0), (133, 134, false));
+        assert_eq!(get_port_equivalents(134, 0), (134, 133, false));
+    }
+
+    #[test]
+    fn neighbor_solicitation_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(135, 0), (135, 136, false));
+        assert_eq!(get_port_equivalents(136, 0), (136, 135, false));
+    }
+
+    #[test]
+    fn wru_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(139, 0), (139, 140, false));
+        assert_eq!(get_port_equivalents(140, 0), (140, 139, false));
+    }
+
+    #[test]
+    fn haad_pair_is_two_way() {
+        assert_eq!(get_port_equivalents(144, 0), (144, 145, false));
+        assert_eq!(get_port_equivalents(145, 0), (145, 144, false));
+    }
+
+    #[test]
+    fn unmapped_type_is_one_way() {
+        // MLDv2 Report (RFC 3810) has no request/reply counterpart.
+        assert_eq!(get_port_equivalents(143, 0), (143, 0, true));
+        assert_eq!(get_port_equivalents(1, 0), (1, 0, true));
+    }
+}
@@ -57,4 +57,4 @@ const IPPROTO_TCP: u8 = 6;
 /// IP Protocol Number of UDP
 const IPPROTO_UDP: u8 = 17;
 
-pub use calc::calculate_community_id;
+pub use calc::{calculate_community_id, community_id};
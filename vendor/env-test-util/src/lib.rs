@@ -1,3 +1,12 @@
+mod providers;
+mod typed;
+
+pub use providers::{
+    DotenvProvider, EnvProvider, ProviderLoadError, ResolveError, Resolver, TomlProvider,
+    VariableProvider,
+};
+pub use typed::{get_env, get_env_or_default, maybe_get_env, EnvError, FromEnvStr};
+
 /// Temporary environment variable manager
 ///
 /// When initialising the variable manager with `new`, the actual content will be removed and stored
@@ -63,6 +72,80 @@ impl Drop for TempEnvVar {
     }
 }
 
+/// Guard that atomically overrides any number of environment variables and restores every one of
+/// them to its prior state when dropped.
+///
+/// Unlike [`TempEnvVar`], `ScopedEnv` is not consumed by `set` and can track several variables at
+/// once, which makes it a better fit for integration tests that need to toggle a whole group of
+/// config env vars together. Scopes nest correctly: an inner `ScopedEnv` only ever records and
+/// restores the state it observed when it called `set`, so an outer scope's own pending changes
+/// are left untouched by the inner scope's drop, as long as scopes are dropped in the order they
+/// were created (the normal case for nested blocks).
+///
+/// # Examples
+///
+/// ```
+/// use env_test_util::ScopedEnv;
+///
+/// std::env::set_var("SCOPED_A", "outer");
+/// std::env::remove_var("SCOPED_B");
+/// {
+///     let mut outer = ScopedEnv::new();
+///     outer.set("SCOPED_A", "outer-new");
+///     {
+///         let mut inner = ScopedEnv::new();
+///         inner.set("SCOPED_A", "inner-new");
+///         inner.set("SCOPED_B", "inner-b");
+///         assert_eq!(std::env::var("SCOPED_A").ok(), Some("inner-new".into()));
+///     }
+///     // inner restored SCOPED_A to what outer had set, and removed SCOPED_B again.
+///     assert_eq!(std::env::var("SCOPED_A").ok(), Some("outer-new".into()));
+///     assert_eq!(std::env::var("SCOPED_B").ok(), None);
+/// }
+/// // outer restored SCOPED_A to its original value.
+/// assert_eq!(std::env::var("SCOPED_A").ok(), Some("outer".into()));
+/// ```
+#[derive(Debug, Default)]
+pub struct ScopedEnv {
+    /// Prior value of each overridden variable, in the order it was first set.
+    prior: Vec<(String, Option<String>)>,
+}
+
+impl ScopedEnv {
+    /// Create an empty scope. Call [`ScopedEnv::set`] to start overriding variables.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Override `key` with `value`, recording its current value so it can be restored on drop.
+    ///
+    /// Setting the same key twice within one scope only records the value from *before* the
+    /// first call, so the scope still restores the pre-scope state rather than an intermediate
+    /// value set earlier in the same scope.
+    pub fn set(&mut self, key: impl Into<String>, value: impl AsRef<str>) -> &mut Self {
+        let key = key.into();
+        if !self.prior.iter().any(|(k, _)| k == &key) {
+            self.prior.push((key.clone(), std::env::var(&key).ok()));
+        }
+        std::env::set_var(&key, value.as_ref());
+        self
+    }
+}
+
+impl Drop for ScopedEnv {
+    fn drop(&mut self) {
+        // Restore in reverse order so that if the same key were ever pushed more than once
+        // (which `set` prevents, but keep this robust against future changes), the earliest
+        // recorded value wins.
+        for (key, value) in self.prior.iter().rev() {
+            match value {
+                Some(value) => std::env::set_var(key, value),
+                None => std::env::remove_var(key),
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -94,4 +177,56 @@ mod tests {
         drop(variable);
         assert_eq!(std::env::var(name).ok(), Some("INITIAL".into()));
     }
+
+    #[test]
+    fn scoped_env_restores_all_variables_together() {
+        std::env::set_var("SCOPED_ENV_A", "initial-a");
+        std::env::remove_var("SCOPED_ENV_B");
+        {
+            let mut scope = ScopedEnv::new();
+            scope.set("SCOPED_ENV_A", "new-a");
+            scope.set("SCOPED_ENV_B", "new-b");
+            assert_eq!(std::env::var("SCOPED_ENV_A").ok(), Some("new-a".into()));
+            assert_eq!(std::env::var("SCOPED_ENV_B").ok(), Some("new-b".into()));
+        }
+        assert_eq!(std::env::var("SCOPED_ENV_A").ok(), Some("initial-a".into()));
+        assert_eq!(std::env::var("SCOPED_ENV_B").ok(), None);
+    }
+
+    #[test]
+    fn scoped_env_nesting_does_not_clobber_outer_scope() {
+        std::env::set_var("SCOPED_ENV_NEST", "outer-initial");
+        {
+            let mut outer = ScopedEnv::new();
+            outer.set("SCOPED_ENV_NEST", "outer-new");
+            {
+                let mut inner = ScopedEnv::new();
+                inner.set("SCOPED_ENV_NEST", "inner-new");
+                assert_eq!(
+                    std::env::var("SCOPED_ENV_NEST").ok(),
+                    Some("inner-new".into())
+                );
+            }
+            assert_eq!(
+                std::env::var("SCOPED_ENV_NEST").ok(),
+                Some("outer-new".into())
+            );
+        }
+        assert_eq!(
+            std::env::var("SCOPED_ENV_NEST").ok(),
+            Some("outer-initial".into())
+        );
+    }
+
+    #[test]
+    fn scoped_env_setting_same_key_twice_keeps_earliest_prior_value() {
+        std::env::set_var("SCOPED_ENV_TWICE", "initial");
+        {
+            let mut scope = ScopedEnv::new();
+            scope.set("SCOPED_ENV_TWICE", "first");
+            scope.set("SCOPED_ENV_TWICE", "second");
+            assert_eq!(std::env::var("SCOPED_ENV_TWICE").ok(), Some("second".into()));
+        }
+        assert_eq!(std::env::var("SCOPED_ENV_TWICE").ok(), Some("initial".into()));
+    }
 }
@@ -0,0 +1,264 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A source that can be asked to resolve a `${VAR}`-style name to a value.
+///
+/// Implementations are consulted in order by [`Resolver`] until one reports a value (or all of
+/// them are exhausted).
+pub trait VariableProvider {
+    /// A short, human-readable name used in [`ResolveError`] to report which providers were
+    /// consulted.
+    fn name(&self) -> &str;
+
+    /// Look up `key`, returning `None` if this provider has no value for it.
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+/// Resolves variables against the current process environment.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct EnvProvider;
+
+impl VariableProvider for EnvProvider {
+    fn name(&self) -> &str {
+        "process environment"
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        std::env::var(key).ok()
+    }
+}
+
+/// Resolves variables from a `.env`-style file (`KEY=VALUE` pairs, `#` comments, and both single-
+/// and double-quoted values), loaded eagerly at construction time.
+#[derive(Debug, Clone)]
+pub struct DotenvProvider {
+    path: PathBuf,
+    values: BTreeMap<String, String>,
+}
+
+impl DotenvProvider {
+    /// Load and parse `path` using dotenvy-compatible syntax.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProviderLoadError> {
+        let path = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path).map_err(|source| ProviderLoadError {
+            path: path.clone(),
+            source: source.to_string(),
+        })?;
+        Ok(Self {
+            values: parse_dotenv(&contents),
+            path,
+        })
+    }
+}
+
+fn parse_dotenv(contents: &str) -> BTreeMap<String, String> {
+    let mut values = BTreeMap::new();
+    for raw_line in contents.lines() {
+        let line = raw_line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let line = line.strip_prefix("export ").unwrap_or(line);
+        let Some((key, value)) = line.split_once('=') else {
+            continue;
+        };
+        let key = key.trim().to_string();
+        let value = unquote(value.trim());
+        values.insert(key, value);
+    }
+    values
+}
+
+fn unquote(value: &str) -> String {
+    let bytes = value.as_bytes();
+    if bytes.len() >= 2
+        && ((bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"')
+            || (bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\''))
+    {
+        value[1..value.len() - 1].to_string()
+    } else {
+        // Strip a trailing, unquoted comment the way dotenvy does.
+        match value.split_once(" #") {
+            Some((value, _)) => value.trim_end().to_string(),
+            None => value.to_string(),
+        }
+    }
+}
+
+impl VariableProvider for DotenvProvider {
+    fn name(&self) -> &str {
+        self.path.to_str().unwrap_or("<dotenv file>")
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+}
+
+/// Resolves variables from a TOML file mapping variable names to string values.
+#[derive(Debug, Clone)]
+pub struct TomlProvider {
+    path: PathBuf,
+    values: BTreeMap<String, String>,
+}
+
+impl TomlProvider {
+    /// Load and parse `path` as a flat `name = "value"` TOML table.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, ProviderLoadError> {
+        let path = path.as_ref().to_path_buf();
+        let contents = fs::read_to_string(&path).map_err(|source| ProviderLoadError {
+            path: path.clone(),
+            source: source.to_string(),
+        })?;
+        let table: BTreeMap<String, toml::Value> =
+            toml::from_str(&contents).map_err(|source| ProviderLoadError {
+                path: path.clone(),
+                source: source.to_string(),
+            })?;
+        let values = table
+            .into_iter()
+            .filter_map(|(key, value)| match value {
+                toml::Value::String(s) => Some((key, s)),
+                other => Some((key, other.to_string())),
+            })
+            .collect();
+        Ok(Self { path, values })
+    }
+}
+
+impl VariableProvider for TomlProvider {
+    fn name(&self) -> &str {
+        self.path.to_str().unwrap_or("<toml file>")
+    }
+
+    fn get(&self, key: &str) -> Option<String> {
+        self.values.get(key).cloned()
+    }
+}
+
+/// An error raised when a provider's backing file can't be read or parsed.
+#[derive(Debug, Clone)]
+pub struct ProviderLoadError {
+    path: PathBuf,
+    source: String,
+}
+
+impl fmt::Display for ProviderLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to load `{}`: {}", self.path.display(), self.source)
+    }
+}
+
+impl std::error::Error for ProviderLoadError {}
+
+/// Queries an ordered chain of [`VariableProvider`]s, returning the first value found.
+///
+/// Providers earlier in the chain take precedence. A typical setup puts [`EnvProvider`] first so
+/// ambient environment variables override values loaded from files.
+pub struct Resolver {
+    providers: Vec<Box<dyn VariableProvider>>,
+}
+
+impl Resolver {
+    /// Build a resolver that consults `providers` in order.
+    pub fn new(providers: Vec<Box<dyn VariableProvider>>) -> Self {
+        Self { providers }
+    }
+
+    /// Resolve `key` against each provider in turn, returning an error listing every provider
+    /// consulted if none of them have a value.
+    pub fn resolve(&self, key: &str) -> Result<String, ResolveError> {
+        for provider in &self.providers {
+            if let Some(value) = provider.get(key) {
+                return Ok(value);
+            }
+        }
+        Err(ResolveError {
+            key: key.to_string(),
+            providers_consulted: self.providers.iter().map(|p| p.name().to_string()).collect(),
+        })
+    }
+}
+
+/// Raised by [`Resolver::resolve`] when no provider in the chain has a value for a variable.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResolveError {
+    key: String,
+    providers_consulted: Vec<String>,
+}
+
+impl fmt::Display for ResolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "could not resolve `${{{}}}`; consulted providers: {}",
+            self.key,
+            self.providers_consulted.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for ResolveError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TempEnvVar;
+    use std::io::Write;
+
+    fn write_temp_file(contents: &str, suffix: &str) -> PathBuf {
+        let mut path = std::env::temp_dir();
+        path.push(format!(
+            "env-test-util-{}-{}{}",
+            std::process::id(),
+            contents.len(),
+            suffix
+        ));
+        let mut file = fs::File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn dotenv_parses_quoted_and_commented_values() {
+        let path = write_temp_file(
+            "# comment\nFOO=bar\nBAZ=\"quoted value\"\nexport QUX='single'\n",
+            ".env",
+        );
+        let provider = DotenvProvider::load(&path).unwrap();
+        assert_eq!(provider.get("FOO"), Some("bar".to_string()));
+        assert_eq!(provider.get("BAZ"), Some("quoted value".to_string()));
+        assert_eq!(provider.get("QUX"), Some("single".to_string()));
+        assert_eq!(provider.get("MISSING"), None);
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn toml_provider_reads_string_values() {
+        let path = write_temp_file("name = \"value\"\nother = \"thing\"\n", ".toml");
+        let provider = TomlProvider::load(&path).unwrap();
+        assert_eq!(provider.get("name"), Some("value".to_string()));
+        assert_eq!(provider.get("other"), Some("thing".to_string()));
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn env_provider_takes_precedence_over_file_provider() {
+        let _var = TempEnvVar::new("RESOLVER_PRECEDENCE").with("from-env");
+        let path = write_temp_file("RESOLVER_PRECEDENCE=from-file\n", ".env");
+        let dotenv = DotenvProvider::load(&path).unwrap();
+        let resolver = Resolver::new(vec![Box::new(EnvProvider), Box::new(dotenv)]);
+        assert_eq!(resolver.resolve("RESOLVER_PRECEDENCE").unwrap(), "from-env");
+        fs::remove_file(path).ok();
+    }
+
+    #[test]
+    fn unresolved_name_lists_providers_consulted() {
+        std::env::remove_var("RESOLVER_MISSING");
+        let resolver = Resolver::new(vec![Box::new(EnvProvider)]);
+        let err = resolver.resolve("RESOLVER_MISSING").unwrap_err();
+        assert!(err.to_string().contains("process environment"));
+    }
+}
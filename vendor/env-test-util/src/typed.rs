@@ -0,0 +1,194 @@
+use std::fmt;
+
+/// A typed value that can be parsed out of an environment variable's raw string content.
+///
+/// Implemented for integers, floats, `bool` (accepting `1`/`0`/`true`/`false`/`yes`/`no`,
+/// case-insensitively) and `Vec<T>` (a comma-separated list of `T`).
+pub trait FromEnvStr: Sized {
+    /// The name used in [`EnvError`] messages when parsing fails.
+    const TYPE_NAME: &'static str;
+
+    /// Parse `value`, returning `None` if it isn't a valid representation of `Self`.
+    fn from_env_str(value: &str) -> Option<Self>;
+}
+
+macro_rules! impl_from_env_str_parse {
+    ($($ty:ty),* $(,)?) => {
+        $(
+            impl FromEnvStr for $ty {
+                const TYPE_NAME: &'static str = stringify!($ty);
+
+                fn from_env_str(value: &str) -> Option<Self> {
+                    value.trim().parse().ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_from_env_str_parse!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize, f32, f64, String);
+
+impl FromEnvStr for bool {
+    const TYPE_NAME: &'static str = "bool";
+
+    fn from_env_str(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" => Some(true),
+            "0" | "false" | "no" => Some(false),
+            _ => None,
+        }
+    }
+}
+
+impl<T: FromEnvStr> FromEnvStr for Vec<T> {
+    const TYPE_NAME: &'static str = "list";
+
+    fn from_env_str(value: &str) -> Option<Self> {
+        value
+            .split(',')
+            .map(|item| T::from_env_str(item.trim()))
+            .collect()
+    }
+}
+
+/// An error produced when an environment variable is missing or fails to parse as the requested
+/// type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EnvError {
+    /// The variable was not set.
+    Missing {
+        /// Name of the missing variable.
+        key: String,
+    },
+    /// The variable was set, but its value could not be parsed as the expected type.
+    Invalid {
+        /// Name of the variable.
+        key: String,
+        /// The raw value that failed to parse.
+        value: String,
+        /// The type the value was expected to parse as.
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for EnvError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Missing { key } => write!(f, "environment variable `{key}` is not set"),
+            Self::Invalid {
+                key,
+                value,
+                expected,
+            } => write!(
+                f,
+                "environment variable `{key}` has value `{value}` which is not a valid {expected}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for EnvError {}
+
+fn parse_env<T: FromEnvStr>(key: &str, value: String) -> Result<T, EnvError> {
+    T::from_env_str(&value).ok_or_else(|| EnvError::Invalid {
+        key: key.to_string(),
+        value,
+        expected: T::TYPE_NAME,
+    })
+}
+
+/// Read a required environment variable, returning an error naming the variable and the expected
+/// type if it is unset or fails to parse.
+pub fn get_env<T: FromEnvStr>(key: &str) -> Result<T, EnvError> {
+    match std::env::var(key) {
+        Ok(value) => parse_env(key, value),
+        Err(_) => Err(EnvError::Missing {
+            key: key.to_string(),
+        }),
+    }
+}
+
+/// Read an optional environment variable. Returns `Ok(None)` if it is unset, but still fails if
+/// it is set to a value that doesn't parse as `T`.
+pub fn maybe_get_env<T: FromEnvStr>(key: &str) -> Result<Option<T>, EnvError> {
+    match std::env::var(key) {
+        Ok(value) => parse_env(key, value).map(Some),
+        Err(_) => Ok(None),
+    }
+}
+
+/// Read an environment variable, falling back to `default` if it is unset. Still fails fast if
+/// the variable is set to a value that doesn't parse as `T`, rather than silently using the
+/// default.
+pub fn get_env_or_default<T: FromEnvStr>(key: &str, default: T) -> Result<T, EnvError> {
+    match maybe_get_env(key)? {
+        Some(value) => Ok(value),
+        None => Ok(default),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::TempEnvVar;
+
+    #[test]
+    fn parses_integers() {
+        let _var = TempEnvVar::new("TYPED_ENV_INT").with("42");
+        assert_eq!(get_env::<u32>("TYPED_ENV_INT").unwrap(), 42);
+    }
+
+    #[test]
+    fn parses_bool_synonyms() {
+        for (raw, expected) in [
+            ("1", true),
+            ("true", true),
+            ("TRUE", true),
+            ("yes", true),
+            ("0", false),
+            ("false", false),
+            ("no", false),
+        ] {
+            let _var = TempEnvVar::new("TYPED_ENV_BOOL").with(raw);
+            assert_eq!(get_env::<bool>("TYPED_ENV_BOOL").unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn parses_comma_separated_list() {
+        let _var = TempEnvVar::new("TYPED_ENV_LIST").with("1,2,3");
+        assert_eq!(get_env::<Vec<u32>>("TYPED_ENV_LIST").unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn missing_required_var_is_an_error() {
+        std::env::remove_var("TYPED_ENV_MISSING");
+        let err = get_env::<u32>("TYPED_ENV_MISSING").unwrap_err();
+        assert_eq!(
+            err,
+            EnvError::Missing {
+                key: "TYPED_ENV_MISSING".into()
+            }
+        );
+    }
+
+    #[test]
+    fn invalid_value_fails_fast_instead_of_falling_back() {
+        let _var = TempEnvVar::new("TYPED_ENV_BAD_NUM").with("not-a-number");
+        let err = get_env_or_default::<u32>("TYPED_ENV_BAD_NUM", 7).unwrap_err();
+        assert_eq!(
+            err,
+            EnvError::Invalid {
+                key: "TYPED_ENV_BAD_NUM".into(),
+                value: "not-a-number".into(),
+                expected: "u32",
+            }
+        );
+    }
+
+    #[test]
+    fn default_is_used_when_unset() {
+        std::env::remove_var("TYPED_ENV_UNSET");
+        assert_eq!(get_env_or_default("TYPED_ENV_UNSET", 7u32).unwrap(), 7);
+    }
+}
@@ -3,6 +3,11 @@ use async_graphql::{InputValueError, InputValueResult, Scalar, ScalarType, Value
 
 #[cfg(feature = "serialize")]
 mod serialize;
+#[cfg(feature = "serialize")]
+pub use serialize::{NoProxyEnvStr, NoProxyGrouped};
+
+mod proxy;
+pub use proxy::{Intercept, Proxy, ProxyAuth};
 
 use cidr_utils::cidr::IpCidr;
 use std::collections::{hash_set::IntoIter, HashSet};
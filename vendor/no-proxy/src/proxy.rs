@@ -0,0 +1,292 @@
+use crate::NoProxy;
+use std::collections::BTreeMap;
+use std::fmt;
+use url::Url;
+
+/// Which requests a [`Proxy`] intercepts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum Intercept {
+    /// Only plain HTTP requests.
+    Http,
+    /// Only HTTPS requests.
+    Https,
+    /// Every request, regardless of scheme.
+    All,
+    /// Only requests for the given URL scheme, e.g. `"ftp"`.
+    Custom(String),
+}
+
+/// How a [`Proxy`] authenticates itself to the proxy server.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serialize", serde(rename_all = "snake_case"))]
+pub enum ProxyAuth {
+    /// HTTP Basic auth.
+    Basic { username: String, password: String },
+    /// A bearer token, sent as an `Authorization: Bearer <token>` header.
+    Bearer(String),
+}
+
+#[cfg(feature = "serialize")]
+mod url_serde {
+    use serde::{de, Deserialize, Deserializer, Serializer};
+    use url::Url;
+
+    pub fn serialize<S>(url: &Url, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(url.as_str())
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Url, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let raw = String::deserialize(deserializer)?;
+        Url::parse(&raw).map_err(de::Error::custom)
+    }
+}
+
+/// A fully-configured proxy: which requests it intercepts, where it lives, how to authenticate to
+/// it, any custom headers to send with every proxied request, and which hosts should bypass it
+/// entirely.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serialize", derive(serde::Serialize, serde::Deserialize))]
+pub struct Proxy {
+    pub intercept: Intercept,
+
+    #[cfg_attr(feature = "serialize", serde(with = "url_serde"))]
+    pub url: Url,
+
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub auth: Option<ProxyAuth>,
+
+    /// Additional headers sent with every request made through this proxy.
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub headers: BTreeMap<String, String>,
+
+    #[cfg_attr(feature = "serialize", serde(default))]
+    pub no_proxy: Option<NoProxy>,
+}
+
+impl Proxy {
+    pub fn new(intercept: Intercept, url: Url) -> Proxy {
+        Proxy {
+            intercept,
+            url,
+            auth: None,
+            headers: BTreeMap::new(),
+            no_proxy: None,
+        }
+    }
+}
+
+/// A map value that may be a single scalar or a sequence of scalars, letting [`Proxy::from_map`]
+/// accept both a plain `BTreeMap<String, String>` (e.g. a captured process environment) and a map
+/// whose values are already lists (e.g. parsed YAML).
+pub trait MapValue {
+    /// The value as a single scalar, if it is one.
+    fn as_value(&self) -> Option<&str>;
+    /// The value as a sequence of scalars, if it is one.
+    fn as_seq(&self) -> Option<Vec<&str>>;
+}
+
+impl MapValue for String {
+    fn as_value(&self) -> Option<&str> {
+        Some(self.as_str())
+    }
+
+    fn as_seq(&self) -> Option<Vec<&str>> {
+        None
+    }
+}
+
+impl MapValue for Vec<String> {
+    fn as_value(&self) -> Option<&str> {
+        None
+    }
+
+    fn as_seq(&self) -> Option<Vec<&str>> {
+        Some(self.iter().map(String::as_str).collect())
+    }
+}
+
+/// Why [`Proxy::from_map`] failed to build a `Proxy` from a map.
+#[derive(Debug)]
+pub enum FromMapError {
+    /// A `*_proxy` key's value was a sequence where a single scalar URL was expected.
+    NotAScalar(String),
+    /// A `*_proxy` key's value didn't parse as a URL.
+    InvalidUrl {
+        value: String,
+        source: url::ParseError,
+    },
+}
+
+impl fmt::Display for FromMapError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotAScalar(key) => write!(f, "expected a single value for `{key}`, got a list"),
+            Self::InvalidUrl { value, source } => {
+                write!(f, "invalid proxy URL `{value}`: {source}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromMapError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::InvalidUrl { source, .. } => Some(source),
+            Self::NotAScalar(_) => None,
+        }
+    }
+}
+
+impl Proxy {
+    /// Builds a `Proxy` from well-known keys matched case-insensitively: `http_proxy`,
+    /// `https_proxy`, `all_proxy`, and `no_proxy`. Modeled on dropshot's `from_map`: the URL keys
+    /// go through [`MapValue::as_value`] and `str::parse`, while `no_proxy` accepts either a
+    /// single comma-joined string (the same spelling used in the `NO_PROXY` env var itself) or an
+    /// actual list, reusing the same splitting behavior as `NoProxyVisitor::visit_str`.
+    ///
+    /// Of `all_proxy`, `https_proxy`, and `http_proxy`, the first one present (in that priority
+    /// order) is used, and its key picks the `Intercept` (`all_proxy` -> `All`, `https_proxy` ->
+    /// `Https`, `http_proxy` -> `Http`). Returns `Ok(None)` if none of those three keys are
+    /// present.
+    pub fn from_map<V: MapValue>(map: &BTreeMap<String, V>) -> Result<Option<Proxy>, FromMapError> {
+        let lower: BTreeMap<String, &V> = map
+            .iter()
+            .map(|(key, value)| (key.to_ascii_lowercase(), value))
+            .collect();
+
+        let candidates = [
+            ("all_proxy", Intercept::All),
+            ("https_proxy", Intercept::Https),
+            ("http_proxy", Intercept::Http),
+        ];
+
+        let Some((key, value, intercept)) = candidates.iter().find_map(|(key, intercept)| {
+            lower
+                .get(*key)
+                .map(|value| (*key, *value, intercept.clone()))
+        }) else {
+            return Ok(None);
+        };
+
+        let url = value
+            .as_value()
+            .ok_or_else(|| FromMapError::NotAScalar(key.to_string()))?;
+        let url = Url::parse(url).map_err(|source| FromMapError::InvalidUrl {
+            value: url.to_string(),
+            source,
+        })?;
+
+        let mut proxy = Proxy::new(intercept, url);
+
+        if let Some(no_proxy) = lower.get("no_proxy") {
+            proxy.no_proxy = Some(if let Some(value) = no_proxy.as_value() {
+                NoProxy::from(value)
+            } else if let Some(seq) = no_proxy.as_seq() {
+                NoProxy::from(seq.into_iter().map(str::to_string).collect::<Vec<_>>())
+            } else {
+                NoProxy::default()
+            });
+        }
+
+        Ok(Some(proxy))
+    }
+
+    /// Alias for [`Proxy::from_map`], named to match its most common use: building a `Proxy`
+    /// directly from a captured process environment (`std::env::vars().collect()`).
+    pub fn from_env_map<V: MapValue>(
+        map: &BTreeMap<String, V>,
+    ) -> Result<Option<Proxy>, FromMapError> {
+        Self::from_map(map)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn round_trips_through_json() {
+        let mut proxy = Proxy::new(Intercept::Https, Url::parse("http://proxy.example.com:8080").unwrap());
+        proxy.auth = Some(ProxyAuth::Basic {
+            username: "alice".to_string(),
+            password: "hunter2".to_string(),
+        });
+        proxy
+            .headers
+            .insert("X-Proxy-Tag".to_string(), "vector".to_string());
+        proxy.no_proxy = Some(NoProxy::from("localhost,10.0.0.0/8"));
+
+        let json = serde_json::to_string(&proxy).unwrap();
+        let result: Proxy = serde_json::from_str(&json).unwrap();
+        assert_eq!(proxy, result);
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn rejects_an_invalid_url() {
+        let json = r#"{"intercept": "all", "url": "not a url"}"#;
+        assert!(serde_json::from_str::<Proxy>(json).is_err());
+    }
+
+    #[test]
+    fn from_map_prefers_all_proxy_and_splits_no_proxy_string() {
+        let mut env = BTreeMap::new();
+        env.insert("HTTP_PROXY".to_string(), "http://http.example.com".to_string());
+        env.insert("ALL_PROXY".to_string(), "http://all.example.com".to_string());
+        env.insert("NO_PROXY".to_string(), "localhost,10.0.0.0/8".to_string());
+
+        let proxy = Proxy::from_map(&env).unwrap().unwrap();
+        assert_eq!(proxy.intercept, Intercept::All);
+        assert_eq!(proxy.url.as_str(), "http://all.example.com/");
+        assert_eq!(proxy.no_proxy, Some(NoProxy::from("localhost,10.0.0.0/8")));
+    }
+
+    #[test]
+    fn from_map_accepts_no_proxy_as_a_list() {
+        let mut env: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        env.insert(
+            "http_proxy".to_string(),
+            vec!["http://http.example.com".to_string()],
+        );
+        env.insert(
+            "no_proxy".to_string(),
+            vec!["localhost".to_string(), "10.0.0.0/8".to_string()],
+        );
+
+        let proxy = Proxy::from_map(&env).unwrap().unwrap();
+        assert_eq!(proxy.intercept, Intercept::Http);
+        assert_eq!(
+            proxy.no_proxy,
+            Some(NoProxy::from(vec![
+                "localhost".to_string(),
+                "10.0.0.0/8".to_string()
+            ]))
+        );
+    }
+
+    #[test]
+    fn from_map_returns_none_without_a_proxy_key() {
+        let env: BTreeMap<String, String> = BTreeMap::new();
+        assert_eq!(Proxy::from_map(&env).unwrap(), None);
+    }
+
+    #[test]
+    fn from_map_surfaces_an_invalid_url_error() {
+        let mut env = BTreeMap::new();
+        env.insert("http_proxy".to_string(), "not a url".to_string());
+        assert!(matches!(
+            Proxy::from_map(&env),
+            Err(FromMapError::InvalidUrl { .. })
+        ));
+    }
+}
@@ -1,6 +1,13 @@
 use crate::NoProxy;
+use crate::NoProxyItem;
+use cidr_utils::cidr::IpCidr;
 use serde::{de, Deserialize, Deserializer};
-use serde::{ser::SerializeSeq, Serialize, Serializer};
+use serde::{
+    ser::{SerializeMap, SerializeSeq},
+    Serialize, Serializer,
+};
+use std::net::IpAddr;
+use std::str::FromStr;
 
 impl Serialize for NoProxy {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
@@ -15,13 +22,87 @@ impl Serialize for NoProxy {
     }
 }
 
+/// Serializes a [`NoProxy`] grouped by matcher kind (`domains`, `ips`, `cidrs`) instead of as one
+/// flat array of stringified entries, mirroring the object form [`NoProxyVisitor::visit_map`]
+/// accepts on the way in. Useful when a config format should state intent explicitly rather than
+/// relying on `NoProxy::from` to re-sniff each entry's kind.
+pub struct NoProxyGrouped<'a>(pub &'a NoProxy);
+
+impl<'a> Serialize for NoProxyGrouped<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut domains = vec![];
+        let mut ips = vec![];
+        let mut cidrs = vec![];
+        for item in self.0.content.iter() {
+            match item {
+                NoProxyItem::Wildcard => domains.push("*".to_string()),
+                // `NoProxy::from` parses a bare address as a single-host CIDR block, so it is
+                // told apart from a real block by whether its text is an address on its own.
+                NoProxyItem::IpCidr(value, _) => {
+                    if value.parse::<IpAddr>().is_ok() {
+                        ips.push(value.clone());
+                    } else {
+                        cidrs.push(value.clone());
+                    }
+                }
+                NoProxyItem::WithDot(value, _, _) => domains.push(value.clone()),
+                NoProxyItem::Plain(value) => {
+                    if value.parse::<IpAddr>().is_ok() {
+                        ips.push(value.clone());
+                    } else {
+                        domains.push(value.clone());
+                    }
+                }
+            }
+        }
+        let mut map = serializer.serialize_map(Some(3))?;
+        map.serialize_entry("domains", &domains)?;
+        map.serialize_entry("ips", &ips)?;
+        map.serialize_entry("cidrs", &cidrs)?;
+        map.end()
+    }
+}
+
+/// Serializes a [`NoProxy`] as a single comma-separated scalar string, matching the spelling
+/// tools actually use for the `NO_PROXY` environment variable, instead of the flat array
+/// `NoProxy`'s own `Serialize` impl produces. A config value serialized this way round-trips
+/// through the ordinary `NoProxy` deserializer, since [`NoProxyVisitor::visit_str`] already
+/// accepts the scalar form — this just makes emitting it a first-class, lossless output choice
+/// rather than always producing a sequence.
+pub struct NoProxyEnvStr<'a>(pub &'a NoProxy);
+
+impl<'a> Serialize for NoProxyEnvStr<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.0.to_string())
+    }
+}
+
+/// The object form accepted by [`NoProxyVisitor::visit_map`]: entries grouped by matcher kind
+/// instead of a single flat list, so config files can declare intent explicitly (e.g. that
+/// `"10.0.0.0/8"` really is a CIDR block, not a domain suffix that happens to look like one).
+#[derive(Deserialize, Default)]
+struct NoProxyObject {
+    #[serde(default)]
+    domains: Vec<String>,
+    #[serde(default)]
+    ips: Vec<String>,
+    #[serde(default)]
+    cidrs: Vec<String>,
+}
+
 struct NoProxyVisitor;
 
 impl<'de> de::Visitor<'de> for NoProxyVisitor {
     type Value = Vec<String>;
 
     fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-        formatter.write_str("string or list of strings")
+        formatter.write_str("string, list of strings, or an object with domains/ips/cidrs")
     }
 
     fn visit_str<E>(self, value: &str) -> Result<Self::Value, E>
@@ -40,6 +121,31 @@ impl<'de> de::Visitor<'de> for NoProxyVisitor {
     {
         Deserialize::deserialize(de::value::SeqAccessDeserializer::new(visitor))
     }
+
+    fn visit_map<M>(self, visitor: M) -> Result<Self::Value, M::Error>
+    where
+        M: de::MapAccess<'de>,
+    {
+        let object: NoProxyObject =
+            Deserialize::deserialize(de::value::MapAccessDeserializer::new(visitor))?;
+
+        for ip in &object.ips {
+            IpAddr::from_str(ip)
+                .map_err(|_| de::Error::custom(format!("`{ip}` is not a valid entry under `ips`")))?;
+        }
+        for cidr in &object.cidrs {
+            IpCidr::from_str(cidr).map_err(|_| {
+                de::Error::custom(format!("`{cidr}` is not a valid entry under `cidrs`"))
+            })?;
+        }
+
+        Ok(object
+            .domains
+            .into_iter()
+            .chain(object.ips)
+            .chain(object.cidrs)
+            .collect())
+    }
 }
 
 impl<'de> Deserialize<'de> for NoProxy {
@@ -71,4 +177,65 @@ mod tests {
         assert_eq!(proxy, result);
         assert_eq!(result.content.len(), 2);
     }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn deserializes_structured_object_form() {
+        let result: NoProxy = serde_json::from_str(
+            r#"{ "domains": ["foo.bar"], "ips": ["1.2.3.4"], "cidrs": ["10.0.0.0/8"] }"#,
+        )
+        .unwrap();
+
+        assert_eq!(result.content.len(), 3);
+        assert!(result
+            .content
+            .contains(&NoProxyItem::Plain("foo.bar".to_string())));
+        assert!(matches!(
+            result
+                .content
+                .iter()
+                .find(|item| matches!(item, NoProxyItem::IpCidr(value, _) if value == "10.0.0.0/8")),
+            Some(NoProxyItem::IpCidr(_, _))
+        ));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn structured_object_form_rejects_a_cidr_that_doesnt_parse() {
+        let result = serde_json::from_str::<NoProxy>(
+            r#"{ "domains": [], "ips": [], "cidrs": ["not-a-cidr"] }"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn structured_object_form_rejects_an_ip_that_doesnt_parse() {
+        let result = serde_json::from_str::<NoProxy>(
+            r#"{ "domains": [], "ips": ["not-an-ip"], "cidrs": [] }"#,
+        );
+        assert!(result.is_err());
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn grouped_serialization_separates_by_matcher_kind() {
+        let proxy = NoProxy::from("foo.bar,1.2.3.4,10.0.0.0/8");
+        let json = serde_json::to_value(NoProxyGrouped(&proxy)).unwrap();
+
+        assert_eq!(json["domains"], serde_json::json!(["foo.bar"]));
+        assert_eq!(json["ips"], serde_json::json!(["1.2.3.4"]));
+        assert_eq!(json["cidrs"], serde_json::json!(["10.0.0.0/8"]));
+    }
+
+    #[cfg(feature = "serialize")]
+    #[test]
+    fn env_str_serializes_as_a_single_scalar_and_round_trips() {
+        let proxy = NoProxy::from("foo.bar,1.2.3.4");
+        let json = serde_json::to_value(NoProxyEnvStr(&proxy)).unwrap();
+        assert!(json.is_string());
+
+        let result: NoProxy = serde_json::from_value(json).unwrap();
+        assert_eq!(proxy, result);
+    }
 }
@@ -1,21 +1,137 @@
-use crate::{Mode, TraitArgs};
+use crate::{Mode, RenameRule, TraitArgs};
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{parse_quote, Error, Ident, ItemTrait, LitStr, TraitBoundModifier, TypeParamBound};
+use syn::{parse_quote, Ident, ItemTrait, LitStr, TraitBoundModifier, TypeParamBound};
+
+/// Generates the runtime case-conversion function applied to every registered name, either the
+/// identity function (no `rename_all` on the trait) or one that re-derives the word boundaries of
+/// the name (splitting on each uppercase letter and on digit runs) and rejoins them per `rule`.
+///
+/// An impl's explicit `#[typetag::serde(name = "...")]` overrides whatever the trait's
+/// `rename_all` would otherwise produce: `TypetagRegistration::name_is_explicit` records whether
+/// the impl-side registration supplied one, and `static_registry` only calls this function when it
+/// didn't.
+fn rename_all_fn(rename_all: Option<RenameRule>) -> TokenStream {
+    let Some(rule) = rename_all else {
+        return quote! {
+            fn typetag_rename_all(name: &'static str) -> typetag::__private::String {
+                typetag::__private::String::from(name)
+            }
+        };
+    };
+
+    let join = match rule {
+        RenameRule::Lower | RenameRule::Upper => quote!(words.concat()),
+        RenameRule::Snake | RenameRule::ScreamingSnake => quote!(words.join("_")),
+        RenameRule::Kebab | RenameRule::ScreamingKebab => quote!(words.join("-")),
+        RenameRule::Pascal => quote!(words.concat()),
+        RenameRule::Camel => quote!(words.concat()),
+    };
+
+    let per_word = match rule {
+        RenameRule::Lower | RenameRule::Snake | RenameRule::Kebab => {
+            quote!(word.to_lowercase())
+        }
+        RenameRule::Upper | RenameRule::ScreamingSnake | RenameRule::ScreamingKebab => {
+            quote!(word.to_uppercase())
+        }
+        RenameRule::Pascal => quote! {
+            {
+                let mut chars = word.chars();
+                match chars.next() {
+                    typetag::__private::Option::Some(first) => {
+                        first.to_uppercase().collect::<typetag::__private::String>()
+                            + &chars.as_str().to_lowercase()
+                    }
+                    typetag::__private::Option::None => typetag::__private::String::new(),
+                }
+            }
+        },
+        RenameRule::Camel => quote! {
+            if index == 0 {
+                word.to_lowercase()
+            } else {
+                let mut chars = word.chars();
+                match chars.next() {
+                    typetag::__private::Option::Some(first) => {
+                        first.to_uppercase().collect::<typetag::__private::String>()
+                            + &chars.as_str().to_lowercase()
+                    }
+                    typetag::__private::Option::None => typetag::__private::String::new(),
+                }
+            }
+        },
+    };
+
+    quote! {
+        fn typetag_rename_all(name: &'static str) -> typetag::__private::String {
+            let mut words: typetag::__private::Vec<typetag::__private::String> = typetag::__private::Vec::new();
+            let mut current = typetag::__private::String::new();
+            let mut prev_is_digit = false;
+            for c in name.chars() {
+                let is_digit = c.is_ascii_digit();
+                let starts_new_word = c.is_uppercase() || (is_digit && !prev_is_digit);
+                if starts_new_word && !current.is_empty() {
+                    words.push(typetag::__private::mem::take(&mut current));
+                }
+                current.push(c);
+                prev_is_digit = is_digit;
+            }
+            if !current.is_empty() {
+                words.push(current);
+            }
+
+            let words: typetag::__private::Vec<typetag::__private::String> = words
+                .into_iter()
+                .enumerate()
+                .map(|(index, word)| #per_word)
+                .collect();
+
+            #join
+        }
+    }
+}
 
 pub(crate) fn expand(args: TraitArgs, mut input: ItemTrait, mode: Mode) -> TokenStream {
-    if mode.de && !input.generics.params.is_empty() {
-        let msg = "deserialization of generic traits is not supported yet; \
-                   use #[typetag::serialize] to generate serialization only";
-        return Error::new_spanned(input.generics, msg).to_compile_error();
+    let has_generics = !input.generics.params.is_empty();
+
+    // The generated registry keys each registration on `TypeId::of::<(T, ..)>()`, which requires
+    // every one of the trait's type parameters to be `'static`. Rather than let that requirement
+    // surface as an opaque `TypeId::of` compile error deep in generated code, require it directly
+    // on the trait's own generics so the user sees the bound where they declared the parameter.
+    if has_generics {
+        let type_params: Vec<_> = input.generics.type_params().map(|tp| tp.ident.clone()).collect();
+        let where_clause = input.generics.make_where_clause();
+        for type_param in &type_params {
+            where_clause
+                .predicates
+                .push(parse_quote!(#type_param: 'static));
+        }
     }
 
-    augment_trait(&mut input, mode);
+    let repr_u64 = match &args {
+        TraitArgs::External { repr_u64, .. }
+        | TraitArgs::Internal { repr_u64, .. }
+        | TraitArgs::Adjacent { repr_u64, .. } => *repr_u64,
+        TraitArgs::Untagged => false,
+    };
+
+    augment_trait(&mut input, mode, repr_u64);
 
     let (serialize_impl, deserialize_impl) = match args {
-        TraitArgs::External => externally_tagged(&input),
-        TraitArgs::Internal { tag } => internally_tagged(tag, &input),
-        TraitArgs::Adjacent { tag, content } => adjacently_tagged(tag, content, &input),
+        TraitArgs::External { rename_all, .. } => {
+            externally_tagged(rename_all, repr_u64, has_generics, &input)
+        }
+        TraitArgs::Internal { tag, rename_all, .. } => {
+            internally_tagged(tag, rename_all, repr_u64, has_generics, &input)
+        }
+        TraitArgs::Adjacent {
+            tag,
+            content,
+            rename_all,
+            ..
+        } => adjacently_tagged(tag, content, rename_all, repr_u64, has_generics, &input),
+        TraitArgs::Untagged => untagged(has_generics, &input),
     };
 
     let object = &input.ident;
@@ -56,7 +172,7 @@ pub(crate) fn expand(args: TraitArgs, mut input: ItemTrait, mode: Mode) -> Token
     }
 
     if mode.de {
-        let registry = build_registry(&input);
+        let registry = build_registry(&input, repr_u64, has_generics);
 
         let is_send = has_supertrait(&input, "Send");
         let is_sync = has_supertrait(&input, "Sync");
@@ -70,14 +186,25 @@ pub(crate) fn expand(args: TraitArgs, mut input: ItemTrait, mode: Mode) -> Token
             ),
         };
 
+        // `ty_generics`/`where_clause` carry the trait's own type parameters (empty for a
+        // non-generic trait), so `Strictest` is implemented per instantiation, e.g.
+        // `Strictest for dyn Component<Foo>` alongside `Strictest for dyn Component<Bar>`.
+        let (ty_impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+        // `Deserialize` additionally needs the `'de` lifetime alongside the trait's own
+        // generics, so it gets its own impl-generics built from a clone with `'de` prepended.
+        let mut de_generics = input.generics.clone();
+        de_generics.params.insert(0, parse_quote!('de));
+        let (de_impl_generics, _, de_where_clause) = de_generics.split_for_impl();
+
         expanded.extend(quote! {
             #registry
 
-            impl typetag::__private::Strictest for dyn #object {
-                type Object = dyn #object + #strictest;
+            impl #ty_impl_generics typetag::__private::Strictest for dyn #object #ty_generics #where_clause {
+                type Object = dyn #object #ty_generics + #strictest;
             }
 
-            impl<'de> typetag::__private::serde::Deserialize<'de> for typetag::__private::Box<dyn #object + #strictest> {
+            impl #de_impl_generics typetag::__private::serde::Deserialize<'de> for typetag::__private::Box<dyn #object #ty_generics + #strictest> #de_where_clause {
                 fn deserialize<D>(deserializer: D) -> typetag::__private::Result<Self, D::Error>
                 where
                     D: typetag::__private::serde::Deserializer<'de>,
@@ -89,13 +216,13 @@ pub(crate) fn expand(args: TraitArgs, mut input: ItemTrait, mode: Mode) -> Token
 
         for marker_traits in others {
             expanded.extend(quote! {
-                impl<'de> typetag::__private::serde::Deserialize<'de> for typetag::__private::Box<dyn #object + #marker_traits> {
+                impl #de_impl_generics typetag::__private::serde::Deserialize<'de> for typetag::__private::Box<dyn #object #ty_generics + #marker_traits> #de_where_clause {
                     fn deserialize<D>(deserializer: D) -> typetag::__private::Result<Self, D::Error>
                     where
                         D: typetag::__private::serde::Deserializer<'de>,
                     {
                         typetag::__private::Result::Ok(
-                            <typetag::__private::Box<dyn #object + #strictest>
+                            <typetag::__private::Box<dyn #object #ty_generics + #strictest>
                                 as typetag::__private::serde::Deserialize<'de>>::deserialize(deserializer)?
                         )
                     }
@@ -107,7 +234,7 @@ pub(crate) fn expand(args: TraitArgs, mut input: ItemTrait, mode: Mode) -> Token
     wrap_in_dummy_const(input, expanded)
 }
 
-fn augment_trait(input: &mut ItemTrait, mode: Mode) {
+fn augment_trait(input: &mut ItemTrait, mode: Mode, repr_u64: bool) {
     if mode.ser {
         input.supertraits.push(parse_quote!(typetag::Serialize));
 
@@ -115,6 +242,13 @@ fn augment_trait(input: &mut ItemTrait, mode: Mode) {
             #[doc(hidden)]
             fn typetag_name(&self) -> &'static str;
         });
+
+        if repr_u64 {
+            input.items.push(parse_quote! {
+                #[doc(hidden)]
+                fn typetag_discriminant(&self) -> u64;
+            });
+        }
     }
 
     if mode.de {
@@ -128,16 +262,47 @@ fn augment_trait(input: &mut ItemTrait, mode: Mode) {
     }
 }
 
-fn build_registry(input: &ItemTrait) -> TokenStream {
+fn build_registry(input: &ItemTrait, repr_u64: bool, has_generics: bool) -> TokenStream {
+    if has_generics {
+        return build_generic_registry(input);
+    }
+
     let vis = &input.vis;
     let object = &input.ident;
 
+    let numeric_registry = if repr_u64 {
+        quote! {
+            #vis struct TypetagNumericRegistration<T> {
+                discriminant: u64,
+                deserializer: T,
+            }
+
+            typetag::__private::inventory::collect!(TypetagNumericRegistration<TypetagFn>);
+
+            impl dyn #object {
+                #[doc(hidden)]
+                #vis const fn typetag_register_numeric<T>(discriminant: u64, deserializer: T) -> TypetagNumericRegistration<T> {
+                    TypetagNumericRegistration { discriminant, deserializer }
+                }
+            }
+        }
+    } else {
+        quote!()
+    };
+
     quote! {
         type TypetagStrictest = <dyn #object as typetag::__private::Strictest>::Object;
         type TypetagFn = typetag::__private::DeserializeFn<TypetagStrictest>;
 
         #vis struct TypetagRegistration<T> {
             name: &'static str,
+            // Set when the impl supplied an explicit `#[typetag::serde(name = "...")]`, so
+            // `static_registry` knows to use `name` as-is instead of running it through the
+            // trait's `rename_all`.
+            name_is_explicit: bool,
+            // Each alias paired with whether it came from an explicit `alias = "..."` (as opposed
+            // to being derived from the trait's `rename_all`), mirroring `name`/`name_is_explicit`.
+            aliases: &'static [(&'static str, bool)],
             deserializer: T,
         }
 
@@ -145,21 +310,105 @@ fn build_registry(input: &ItemTrait) -> TokenStream {
 
         impl dyn #object {
             #[doc(hidden)]
-            #vis const fn typetag_register<T>(name: &'static str, deserializer: T) -> TypetagRegistration<T> {
-                TypetagRegistration { name, deserializer }
+            #vis const fn typetag_register<T>(
+                name: &'static str,
+                name_is_explicit: bool,
+                aliases: &'static [(&'static str, bool)],
+                deserializer: T,
+            ) -> TypetagRegistration<T> {
+                TypetagRegistration { name, name_is_explicit, aliases, deserializer }
             }
         }
+
+        #numeric_registry
     }
 }
 
-fn static_registry() -> TokenStream {
+/// Per-instantiation registry for a generic trait, e.g. `trait Component<T>`. `inventory` needs a
+/// single concrete type to collect into, so `TypetagRegistration` here is *not* generic over the
+/// trait's type parameters; instead every registration carries a `key: TypeId` identifying which
+/// instantiation it belongs to (the impl-side registration, generated outside this file, is
+/// expected to supply `TypeId::of::<(T, ..)>()` — a tuple of all the trait's type parameters as
+/// bound by that impl), and an erased deserializer producing `Box<dyn Any>` rather than the
+/// concrete `Box<dyn Component<T> + Strictest>`. The `Any` box is downcast back to the concrete
+/// type in `static_generic_registry`'s caller, which knows `T` because it runs inside the
+/// per-instantiation generic `deserialize` impl.
+fn build_generic_registry(input: &ItemTrait) -> TokenStream {
+    let vis = &input.vis;
+    let object = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    quote! {
+        type TypetagStrictest #impl_generics = <dyn #object #ty_generics as typetag::__private::Strictest>::Object #where_clause;
+        type TypetagErasedFn = fn(
+            &mut dyn typetag::__private::erased_serde::Deserializer,
+        ) -> typetag::__private::Result<typetag::__private::Box<dyn typetag::__private::Any>, typetag::__private::erased_serde::Error>;
+
+        #vis struct TypetagRegistration<T> {
+            key: typetag::__private::TypeId,
+            name: &'static str,
+            deserializer: T,
+        }
+
+        typetag::__private::inventory::collect!(TypetagRegistration<TypetagErasedFn>);
+
+        impl dyn #object #ty_generics {
+            #[doc(hidden)]
+            #vis const fn typetag_register_generic(
+                key: typetag::__private::TypeId,
+                name: &'static str,
+                deserializer: TypetagErasedFn,
+            ) -> TypetagRegistration<TypetagErasedFn> {
+                TypetagRegistration { key, name, deserializer }
+            }
+        }
+    }
+}
+
+/// Builds the string-keyed `Registry`, and, when `repr_u64` is set on the trait, a second
+/// `BTreeMap<u64, Option<DeserializeFn>>` built from the `TypetagNumericRegistration` inventory in
+/// the same duplicate-collapses-to-`None` fashion. The numeric registry is what
+/// `externally`/`internally`/`adjacently::deserialize_numeric` consult instead of the name-keyed
+/// one when the trait opted into compact integer tags.
+fn static_registry(rename_all: Option<RenameRule>, repr_u64: bool) -> TokenStream {
+    let rename_all_fn = rename_all_fn(rename_all);
+
+    let numeric_registry = if repr_u64 {
+        quote! {
+            static TYPETAG_NUMERIC: typetag::__private::once_cell::race::OnceBox<typetag::__private::BTreeMap<u64, typetag::__private::Option<TypetagFn>>> = typetag::__private::once_cell::race::OnceBox::new();
+            let numeric_registry = TYPETAG_NUMERIC.get_or_init(|| {
+                let mut map = typetag::__private::BTreeMap::new();
+                for registered in typetag::__private::inventory::iter::<TypetagNumericRegistration<TypetagFn>> {
+                    match map.entry(registered.discriminant) {
+                        typetag::__private::btree_map::Entry::Vacant(entry) => {
+                            entry.insert(typetag::__private::Option::Some(registered.deserializer));
+                        }
+                        typetag::__private::btree_map::Entry::Occupied(mut entry) => {
+                            entry.insert(typetag::__private::Option::None);
+                        }
+                    }
+                }
+                typetag::__private::Box::new(map)
+            });
+        }
+    } else {
+        quote!()
+    };
+
     quote! {
         static TYPETAG: typetag::__private::once_cell::race::OnceBox<typetag::__private::Registry<TypetagStrictest>> = typetag::__private::once_cell::race::OnceBox::new();
         let registry = TYPETAG.get_or_init(|| {
+            #rename_all_fn
+
             let mut map = typetag::__private::BTreeMap::new();
             let mut names = typetag::__private::Vec::new();
             for registered in typetag::__private::inventory::iter::<TypetagRegistration<TypetagFn>> {
-                match map.entry(registered.name) {
+                let name = if registered.name_is_explicit {
+                    registered.name
+                } else {
+                    typetag::__private::Box::leak(typetag_rename_all(registered.name).into_boxed_str()) as &'static str
+                };
+                match map.entry(name) {
                     typetag::__private::btree_map::Entry::Vacant(entry) => {
                         entry.insert(typetag::__private::Option::Some(registered.deserializer));
                     }
@@ -167,47 +416,177 @@ fn static_registry() -> TokenStream {
                         entry.insert(typetag::__private::Option::None);
                     }
                 }
-                names.push(registered.name);
+                names.push(name);
+
+                // Aliases resolve to the same deserializer but never appear in `names`, so error
+                // messages only ever advertise the canonical name. An alias claimed by two
+                // different impls (or by an impl's alias colliding with another impl's name)
+                // still collapses to `None`, same as any other duplicate.
+                for (alias, alias_is_explicit) in registered.aliases {
+                    let alias = if *alias_is_explicit {
+                        *alias
+                    } else {
+                        typetag::__private::Box::leak(typetag_rename_all(*alias).into_boxed_str()) as &'static str
+                    };
+                    match map.entry(alias) {
+                        typetag::__private::btree_map::Entry::Vacant(entry) => {
+                            entry.insert(typetag::__private::Option::Some(registered.deserializer));
+                        }
+                        typetag::__private::btree_map::Entry::Occupied(mut entry) => {
+                            entry.insert(typetag::__private::Option::None);
+                        }
+                    }
+                }
             }
             names.sort_unstable();
             typetag::__private::Box::new(typetag::__private::Registry { map, names })
         });
+
+        #numeric_registry
     }
 }
 
-fn externally_tagged(input: &ItemTrait) -> (TokenStream, TokenStream) {
+/// Generic counterpart to `static_registry`. This is spliced into a `deserialize<D>` function body
+/// that is itself generic over the trait's type parameters, so the `static` declared here gets its
+/// own storage per monomorphization (statics inside generic functions are monomorphized along with
+/// the function) — no explicit per-`T` cache is needed beyond that. At init time it filters the
+/// single, non-generic `TypetagRegistration<TypetagErasedFn>` inventory down to just the entries
+/// whose `key` matches this instantiation's `TypeId`, then builds the same name -> deserializer map
+/// `static_registry` would, collapsing duplicate names within this instantiation to `None`.
+///
+/// `TypeId::of` requires every spliced-in type parameter to be `'static`; `expand` adds that bound
+/// to the trait's own where clause before this function ever runs, so it's already guaranteed here.
+fn static_generic_registry(input: &ItemTrait) -> TokenStream {
+    let type_params: Vec<_> = input.generics.type_params().map(|tp| &tp.ident).collect();
+
+    quote! {
+        static TYPETAG: typetag::__private::once_cell::race::OnceBox<
+            typetag::__private::BTreeMap<&'static str, typetag::__private::Option<TypetagErasedFn>>,
+        > = typetag::__private::once_cell::race::OnceBox::new();
+        let registry = TYPETAG.get_or_init(|| {
+            let key = typetag::__private::TypeId::of::<(#(#type_params,)*)>();
+
+            let mut map = typetag::__private::BTreeMap::new();
+            for registered in typetag::__private::inventory::iter::<TypetagRegistration<TypetagErasedFn>> {
+                if registered.key != key {
+                    continue;
+                }
+                match map.entry(registered.name) {
+                    typetag::__private::btree_map::Entry::Vacant(entry) => {
+                        entry.insert(typetag::__private::Option::Some(registered.deserializer));
+                    }
+                    typetag::__private::btree_map::Entry::Occupied(mut entry) => {
+                        entry.insert(typetag::__private::Option::None);
+                    }
+                }
+            }
+            typetag::__private::Box::new(map)
+        });
+    }
+}
+
+fn externally_tagged(
+    rename_all: Option<RenameRule>,
+    repr_u64: bool,
+    has_generics: bool,
+    input: &ItemTrait,
+) -> (TokenStream, TokenStream) {
     let object = &input.ident;
     let object_name = object.to_string();
     let (_, ty_generics, _) = input.generics.split_for_impl();
-    let static_registry = static_registry();
 
-    let serialize_impl = quote! {
-        let name = <Self as #object #ty_generics>::typetag_name(self);
-        typetag::__private::externally::serialize(serializer, name, self)
+    let serialize_impl = if repr_u64 {
+        quote! {
+            let discriminant = <Self as #object #ty_generics>::typetag_discriminant(self);
+            typetag::__private::externally::serialize_numeric(serializer, discriminant, self)
+        }
+    } else {
+        let rename_all_fn = rename_all_fn(rename_all);
+        quote! {
+            #rename_all_fn
+            let name = typetag::__private::Box::leak(
+                typetag_rename_all(<Self as #object #ty_generics>::typetag_name(self)).into_boxed_str(),
+            ) as &'static str;
+            typetag::__private::externally::serialize(serializer, name, self)
+        }
     };
 
-    let deserialize_impl = quote! {
-        #static_registry
-        typetag::__private::externally::deserialize(deserializer, #object_name, registry)
+    let deserialize_impl = if has_generics {
+        let static_registry = static_generic_registry(input);
+        quote! {
+            #static_registry
+            let value = typetag::__private::externally::deserialize_erased(deserializer, #object_name, registry)?;
+            typetag::__private::Result::Ok(
+                *value.downcast::<Self>().expect("typetag: registered deserializer produced the wrong concrete type for this instantiation")
+            )
+        }
+    } else {
+        let static_registry = static_registry(rename_all, repr_u64);
+        if repr_u64 {
+            quote! {
+                #static_registry
+                typetag::__private::externally::deserialize_numeric(deserializer, #object_name, numeric_registry)
+            }
+        } else {
+            quote! {
+                #static_registry
+                typetag::__private::externally::deserialize(deserializer, #object_name, registry)
+            }
+        }
     };
 
     (serialize_impl, deserialize_impl)
 }
 
-fn internally_tagged(tag: LitStr, input: &ItemTrait) -> (TokenStream, TokenStream) {
+fn internally_tagged(
+    tag: LitStr,
+    rename_all: Option<RenameRule>,
+    repr_u64: bool,
+    has_generics: bool,
+    input: &ItemTrait,
+) -> (TokenStream, TokenStream) {
     let object = &input.ident;
     let object_name = object.to_string();
     let (_, ty_generics, _) = input.generics.split_for_impl();
-    let static_registry = static_registry();
 
-    let serialize_impl = quote! {
-        let name = <Self as #object #ty_generics>::typetag_name(self);
-        typetag::__private::internally::serialize(serializer, #tag, name, self)
+    let serialize_impl = if repr_u64 {
+        quote! {
+            let discriminant = <Self as #object #ty_generics>::typetag_discriminant(self);
+            typetag::__private::internally::serialize_numeric(serializer, #tag, discriminant, self)
+        }
+    } else {
+        let rename_all_fn = rename_all_fn(rename_all);
+        quote! {
+            #rename_all_fn
+            let name = typetag::__private::Box::leak(
+                typetag_rename_all(<Self as #object #ty_generics>::typetag_name(self)).into_boxed_str(),
+            ) as &'static str;
+            typetag::__private::internally::serialize(serializer, #tag, name, self)
+        }
     };
 
-    let deserialize_impl = quote! {
-        #static_registry
-        typetag::__private::internally::deserialize(deserializer, #object_name, #tag, registry)
+    let deserialize_impl = if has_generics {
+        let static_registry = static_generic_registry(input);
+        quote! {
+            #static_registry
+            let value = typetag::__private::internally::deserialize_erased(deserializer, #object_name, #tag, registry)?;
+            typetag::__private::Result::Ok(
+                *value.downcast::<Self>().expect("typetag: registered deserializer produced the wrong concrete type for this instantiation")
+            )
+        }
+    } else {
+        let static_registry = static_registry(rename_all, repr_u64);
+        if repr_u64 {
+            quote! {
+                #static_registry
+                typetag::__private::internally::deserialize_numeric(deserializer, #object_name, #tag, numeric_registry)
+            }
+        } else {
+            quote! {
+                #static_registry
+                typetag::__private::internally::deserialize(deserializer, #object_name, #tag, registry)
+            }
+        }
     };
 
     (serialize_impl, deserialize_impl)
@@ -216,21 +595,103 @@ fn internally_tagged(tag: LitStr, input: &ItemTrait) -> (TokenStream, TokenStrea
 fn adjacently_tagged(
     tag: LitStr,
     content: LitStr,
+    rename_all: Option<RenameRule>,
+    repr_u64: bool,
+    has_generics: bool,
     input: &ItemTrait,
 ) -> (TokenStream, TokenStream) {
     let object = &input.ident;
     let object_name = object.to_string();
     let (_, ty_generics, _) = input.generics.split_for_impl();
-    let static_registry = static_registry();
+
+    let serialize_impl = if repr_u64 {
+        quote! {
+            let discriminant = <Self as #object #ty_generics>::typetag_discriminant(self);
+            typetag::__private::adjacently::serialize_numeric(serializer, #object_name, #tag, discriminant, #content, self)
+        }
+    } else {
+        let rename_all_fn = rename_all_fn(rename_all);
+        quote! {
+            #rename_all_fn
+            let name = typetag::__private::Box::leak(
+                typetag_rename_all(<Self as #object #ty_generics>::typetag_name(self)).into_boxed_str(),
+            ) as &'static str;
+            typetag::__private::adjacently::serialize(serializer, #object_name, #tag, name, #content, self)
+        }
+    };
+
+    let deserialize_impl = if has_generics {
+        let static_registry = static_generic_registry(input);
+        quote! {
+            #static_registry
+            let value = typetag::__private::adjacently::deserialize_erased(deserializer, #object_name, &[#tag, #content], registry)?;
+            typetag::__private::Result::Ok(
+                *value.downcast::<Self>().expect("typetag: registered deserializer produced the wrong concrete type for this instantiation")
+            )
+        }
+    } else {
+        let static_registry = static_registry(rename_all, repr_u64);
+        if repr_u64 {
+            quote! {
+                #static_registry
+                typetag::__private::adjacently::deserialize_numeric(deserializer, #object_name, &[#tag, #content], numeric_registry)
+            }
+        } else {
+            quote! {
+                #static_registry
+                typetag::__private::adjacently::deserialize(deserializer, #object_name, &[#tag, #content], registry)
+            }
+        }
+    };
+
+    (serialize_impl, deserialize_impl)
+}
+
+/// Builds the registry `untagged` deserialization tries against: every `DeserializeFn` in
+/// registration order, with no name or discriminant attached. Unlike `static_registry`, duplicate
+/// registrations aren't collapsed to `None` here — there's no tag to collide on, so every impl
+/// just gets its own turn at the buffered `Content`.
+fn untagged_registry() -> TokenStream {
+    quote! {
+        static TYPETAG_UNTAGGED: typetag::__private::once_cell::race::OnceBox<typetag::__private::Vec<TypetagFn>> = typetag::__private::once_cell::race::OnceBox::new();
+        let registry = TYPETAG_UNTAGGED.get_or_init(|| {
+            let fns = typetag::__private::inventory::iter::<TypetagRegistration<TypetagFn>>
+                .into_iter()
+                .map(|registered| registered.deserializer)
+                .collect();
+            typetag::__private::Box::new(fns)
+        });
+    }
+}
+
+/// No tag wrapper at all: serialize emits the inner value directly, and deserialize buffers the
+/// input into a self-describing `Content` (the same buffering serde's own `#[serde(untagged)]`
+/// relies on) and tries each registered impl's `DeserializeFn` in turn against a fresh
+/// `ContentDeserializer` clone, returning the first success. Ambiguous input that more than one
+/// impl would happily accept resolves to whichever was registered first; failures from every impl
+/// are collected and reported together so the error at least lists what was tried.
+fn untagged(has_generics: bool, input: &ItemTrait) -> (TokenStream, TokenStream) {
+    let object_name = input.ident.to_string();
 
     let serialize_impl = quote! {
-        let name = <Self as #object #ty_generics>::typetag_name(self);
-        typetag::__private::adjacently::serialize(serializer, #object_name, #tag, name, #content, self)
+        typetag::__private::untagged::serialize(serializer, self)
     };
 
-    let deserialize_impl = quote! {
-        #static_registry
-        typetag::__private::adjacently::deserialize(deserializer, #object_name, &[#tag, #content], registry)
+    let deserialize_impl = if has_generics {
+        let static_registry = static_generic_registry(input);
+        quote! {
+            #static_registry
+            let value = typetag::__private::untagged::deserialize_erased(deserializer, #object_name, registry)?;
+            typetag::__private::Result::Ok(
+                *value.downcast::<Self>().expect("typetag: registered deserializer produced the wrong concrete type for this instantiation")
+            )
+        }
+    } else {
+        let untagged_registry = untagged_registry();
+        quote! {
+            #untagged_registry
+            typetag::__private::untagged::deserialize(deserializer, #object_name, registry)
+        }
     };
 
     (serialize_impl, deserialize_impl)